@@ -0,0 +1,84 @@
+use crate::client::{Cache, Client};
+use crate::error::{Error, FetchError, Result};
+use crate::html;
+
+/// The account that a session cookie belongs to.
+#[derive(Debug, PartialEq, Eq)]
+pub struct User {
+    pub name: String,
+    /// Stars in the event whose page was requested, not shown for events without any.
+    pub stars: Option<u32>,
+}
+
+/// Requests the page of the event, which shows the logged in user in its header. Advent of Code
+/// doesn't reject invalid session cookies there, but shows the page as if logged out.
+pub fn check_session(client: &Client, year: u16) -> Result<User> {
+    client.require_session()?;
+
+    let url = client.url(&format!("/{year}"));
+    let html = client.get(&url, Cache::Refresh)?;
+    parse_user(&html).ok_or(Error::Fetch {
+        url,
+        error: FetchError::InvalidSession,
+    })
+}
+
+/// Reads the user from `<div class="user">name <span class="star-count">50*</span></div>`,
+/// which can also contain a supporter badge after the name.
+fn parse_user(html: &str) -> Option<User> {
+    let tokens = html::tokenize(html);
+    let start = tokens
+        .iter()
+        .position(|token| token.is_start("div") && token.attribute("class") == Some("user"))?;
+
+    let mut name = None;
+    let mut stars = None;
+    let mut in_star_count = false;
+    for token in tokens[start + 1..].iter().take_while(|t| !t.is_end("div")) {
+        match token {
+            html::Token::Start { .. } if token.is_start("span") => {
+                in_star_count = token.attribute("class") == Some("star-count");
+            }
+            html::Token::End(_) if token.is_end("span") => in_star_count = false,
+            html::Token::Text(text) if in_star_count => {
+                stars = text.trim().trim_end_matches('*').parse().ok();
+            }
+            html::Token::Text(text) if name.is_none() && !text.trim().is_empty() => {
+                name = Some(text.trim().to_string());
+            }
+            _ => {}
+        }
+    }
+
+    Some(User { name: name?, stars })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_user() {
+        let user = parse_user(include_str!("../fixtures/calendar.html"));
+        assert_eq!(
+            user,
+            Some(User {
+                name: "Alice".to_string(),
+                stars: Some(3)
+            })
+        );
+
+        let html = r#"<header><div class="user">(anonymous user #1003) <a href="/2024/support" class="supporter-badge">(AoC++)</a></div></header>"#;
+        assert_eq!(
+            parse_user(html),
+            Some(User {
+                name: "(anonymous user #1003)".to_string(),
+                stars: None
+            })
+        );
+        assert_eq!(
+            parse_user("<header><a href=\"/auth/login\">[Log In]</a></header>"),
+            None
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+
+use aoc_utils::{day_dir, input_file_name};
+
+use crate::client::{Cache, Client};
+use crate::crypt;
+use crate::date;
+use crate::error::{Error, FetchError, IoContext, Result};
+use crate::fetch::fetch_input;
+use crate::scaffold;
+
+/// What happened to the input of a day while backfilling.
+pub enum InputStatus {
+    Present,
+    Downloaded {
+        lines: usize,
+    },
+    /// Inputs are only written into existing crates, otherwise the workspace would have a member without Cargo.toml.
+    NoCrate,
+    Failed(Error),
+}
+
+impl InputStatus {
+    /// Whether the following downloads would fail the same way, e.g. with an expired session or
+    /// because the inputs wouldn't be encrypted.
+    pub fn stops_backfill(&self) -> bool {
+        matches!(
+            self,
+            InputStatus::Failed(Error::Fetch {
+                error: FetchError::InvalidSession,
+                ..
+            }) | InputStatus::Failed(Error::NotEncrypted { .. })
+        )
+    }
+}
+
+impl Display for InputStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputStatus::Present => write!(f, "present"),
+            InputStatus::Downloaded { lines } => write!(f, "downloaded ({lines} lines)"),
+            InputStatus::NoCrate => write!(f, "no crate, create it using the new command"),
+            InputStatus::Failed(err) => write!(f, "failed: {err}"),
+        }
+    }
+}
+
+/// Years for which the workspace contains a directory, e.g. `y2024`.
+pub fn find_years() -> Result<Vec<u16>> {
+    let mut years: Vec<u16> = fs::read_dir(".")
+        .context("Could not read workspace directory")?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix('y')?.parse().ok()
+        })
+        .collect();
+    years.sort();
+    Ok(years)
+}
+
+/// Days of the event of the year that are already unlocked.
+pub fn unlocked_days(year: u16) -> impl Iterator<Item = u8> {
+    let now = date::now();
+    (1..=date::event_days(year)).filter(move |day| date::unlock_time(year, *day) <= now)
+}
+
+/// Downloads the input of the day into its crate, if the crate exists and the input is missing or empty.
+pub fn backfill_input(
+    client: &Client,
+    year: u16,
+    day: u8,
+    force: bool,
+    allow_unencrypted: bool,
+) -> InputStatus {
+    let day_dir = day_dir(year, day);
+    if !day_dir.join("Cargo.toml").exists() {
+        return InputStatus::NoCrate;
+    }
+
+    let input_path = day_dir.join(input_file_name(day));
+    let present = fs::metadata(&input_path).is_ok_and(|metadata| metadata.len() > 0);
+    if present && !force {
+        return InputStatus::Present;
+    }
+    if let Err(err) = crypt::guard(&input_path, allow_unencrypted) {
+        return InputStatus::Failed(err);
+    }
+
+    // With force the input is downloaded again, in case the cached one is broken as well.
+    let cache = if force { Cache::Refresh } else { Cache::Use };
+    match fetch_input(client, year, day, cache).and_then(|input| write_input(day, &day_dir, input))
+    {
+        Ok(lines) => InputStatus::Downloaded { lines },
+        Err(err) => InputStatus::Failed(err),
+    }
+}
+
+fn write_input(day: u8, day_dir: &Path, input: Option<String>) -> Result<usize> {
+    let input = input.unwrap_or_default();
+    scaffold::create_input_file(day, day_dir, Some(&input))?;
+    Ok(input.lines().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlocked_days() {
+        assert_eq!(unlocked_days(2015).count(), 25);
+        assert_eq!(unlocked_days(2025).count(), 12);
+        assert_eq!(unlocked_days(9999).count(), 0);
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::time::Duration;
+
+use aoc_utils::{day_dir, input_file_name};
+
+use crate::client::{Cache, Client};
+use crate::date;
+use crate::error::Result;
+use crate::history::History;
+use crate::html;
+use crate::submit::{Outcome, Part};
+
+/// The calendar only changes when a star is earned, so it doesn't need to be requested often.
+const CALENDAR_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// How far the puzzle of a day is solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: u8,
+    pub has_crate: bool,
+    pub has_input: bool,
+    /// Parts with an answer that was accepted according to the submission history.
+    pub verified: u8,
+    /// Stars of the day, from the calendar of the event if it was downloaded, otherwise the verified parts.
+    pub stars: u8,
+}
+
+/// Collects the progress of every day of the event from the workspace and the submission histories.
+/// A history that can't be read, e.g. because the repository isn't unlocked, counts as no verified parts.
+pub fn collect(year: u16, calendar: Option<&HashMap<u8, u8>>) -> Vec<DayProgress> {
+    (1..=date::event_days(year))
+        .map(|day| {
+            let day_dir = day_dir(year, day);
+            let input = day_dir.join(input_file_name(day));
+            let submissions = History::load(&day_dir)
+                .map(|history| history.submissions)
+                .unwrap_or_default();
+            let verified = [Part::A, Part::B]
+                .into_iter()
+                .filter(|part| {
+                    submissions
+                        .iter()
+                        .any(|s| s.part == *part && s.outcome == Outcome::Correct)
+                })
+                .count() as u8;
+            let calendar_stars = calendar.and_then(|calendar| calendar.get(&day)).copied();
+
+            DayProgress {
+                day,
+                has_crate: day_dir.join("Cargo.toml").exists(),
+                has_input: fs::metadata(&input).is_ok_and(|metadata| metadata.len() > 0),
+                verified,
+                stars: calendar_stars.unwrap_or(0).max(verified),
+            }
+        })
+        .collect()
+}
+
+/// Downloads the calendar of the event, which shows the stars of the logged in user.
+pub fn fetch_calendar(client: &Client, year: u16) -> Result<HashMap<u8, u8>> {
+    let html = client.get(
+        &client.url(&format!("/{year}")),
+        Cache::MaxAge(CALENDAR_MAX_AGE),
+    )?;
+    Ok(parse_calendar(&html))
+}
+
+/// Reads the stars of each day from the links of the calendar, whose classes are
+/// `calendar-complete` for one star and `calendar-verycomplete` for both.
+fn parse_calendar(html: &str) -> HashMap<u8, u8> {
+    html::tokenize(html)
+        .iter()
+        .filter(|token| token.is_start("a"))
+        .filter_map(|token| {
+            let classes: Vec<&str> = token.attribute("class")?.split_whitespace().collect();
+            let day = classes
+                .iter()
+                .find_map(|class| class.strip_prefix("calendar-day")?.parse().ok())?;
+            let stars = if classes.contains(&"calendar-verycomplete") {
+                2
+            } else if classes.contains(&"calendar-complete") {
+                1
+            } else {
+                0
+            };
+            Some((day, stars))
+        })
+        .collect()
+}
+
+fn stars(count: u8) -> &'static str {
+    match count {
+        0 => "  ",
+        1 => "* ",
+        _ => "**",
+    }
+}
+
+/// Renders the days as a calendar with five days per row. Each day shows its stars and whether it
+/// has a crate (`c`), an input (`i`) and verified answers (`v`).
+pub fn render_grid(year: u16, days: &[DayProgress]) -> String {
+    let mut out = String::new();
+    let total: u32 = days.iter().map(|day| u32::from(day.stars)).sum();
+    writeln!(out, "Advent of Code {year}: {total} stars").unwrap();
+
+    for week in days.chunks(5) {
+        let cells: Vec<String> = week
+            .iter()
+            .map(|day| {
+                let flag = |set: bool, c: char| if set { c } else { '-' };
+                format!(
+                    "{:>2} {} {}{}{}",
+                    day.day,
+                    stars(day.stars),
+                    flag(day.has_crate, 'c'),
+                    flag(day.has_input, 'i'),
+                    flag(day.verified > 0, 'v')
+                )
+            })
+            .collect();
+        writeln!(out, "{}", cells.join("   ")).unwrap();
+    }
+    writeln!(out, "c: crate, i: input, v: verified answer").unwrap();
+    out
+}
+
+/// Renders a Markdown table of the days that have a crate or stars.
+pub fn readme_table(year: u16, days: &[DayProgress]) -> String {
+    let mut out = String::new();
+    writeln!(out, "| Day | Solution | Stars |").unwrap();
+    writeln!(out, "| --: | -------- | ----- |").unwrap();
+    for day in days.iter().filter(|day| day.has_crate || day.stars > 0) {
+        let dir = day_dir(year, day.day);
+        let solution = if day.has_crate {
+            format!("[{0}]({0}/src/main.rs)", dir.display())
+        } else {
+            "-".to_string()
+        };
+        writeln!(
+            out,
+            "| {} | {solution} | {} |",
+            day.day,
+            stars(day.stars).trim_end()
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Replaces the progress table of the year in the README, between its start and end markers.
+/// If there is none yet, a section with the table is appended.
+pub fn update_readme(readme: &str, year: u16, table: &str) -> String {
+    let start = format!("<!-- progress {year} -->\n");
+    let end = format!("<!-- /progress {year} -->");
+
+    if let Some((before, rest)) = readme.split_once(&start) {
+        if let Some((_, after)) = rest.split_once(&end) {
+            return format!("{before}{start}{table}{end}{after}");
+        }
+    }
+
+    let separator = if readme.ends_with('\n') { "" } else { "\n" };
+    format!("{readme}{separator}\n## Progress {year}\n\n{start}{table}{end}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u8, has_crate: bool, stars: u8) -> DayProgress {
+        DayProgress {
+            day,
+            has_crate,
+            has_input: has_crate,
+            verified: stars,
+            stars,
+        }
+    }
+
+    #[test]
+    fn test_parse_calendar() {
+        let calendar = parse_calendar(include_str!("../fixtures/calendar.html"));
+        assert_eq!(calendar, HashMap::from([(1, 2), (2, 1), (3, 0)]));
+    }
+
+    #[test]
+    fn test_render_grid() {
+        let days: Vec<DayProgress> = (1..=6).map(|d| day(d, d <= 2, 3 - d.min(3))).collect();
+        assert_eq!(
+            render_grid(2024, &days),
+            "Advent of Code 2024: 3 stars\n 1 ** civ    2 *  civ    3    ---    4    ---    5    ---\n 6    ---\nc: crate, i: input, v: verified answer\n"
+        );
+    }
+
+    #[test]
+    fn test_update_readme() {
+        let table = readme_table(2024, &[day(1, true, 2), day(2, false, 0), day(3, false, 1)]);
+        assert_eq!(
+            table,
+            "| Day | Solution | Stars |\n| --: | -------- | ----- |\n| 1 | [y2024/day01](y2024/day01/src/main.rs) | ** |\n| 3 | - | * |\n"
+        );
+
+        let readme = update_readme("# AoC\n", 2024, "old\n");
+        assert_eq!(
+            readme,
+            "# AoC\n\n## Progress 2024\n\n<!-- progress 2024 -->\nold\n<!-- /progress 2024 -->\n"
+        );
+        assert_eq!(
+            update_readme(&readme, 2024, "new\n"),
+            readme.replace("old", "new")
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use std::fs;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::date;
+use crate::error::{Error, FetchError, IoContext, Result};
+
+/// Identifies the requests of this tool, as Advent of Code asks automated tools to do.
+/// Set `AOC_USER_AGENT` to include your contact information.
+const DEFAULT_USER_AGENT: &str = concat!(
+    "add-day/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/hlxid/AdventOfCode2024)"
+);
+/// Advent of Code itself. Set `AOC_URL` to send the requests to another server, e.g. a mock server.
+const DEFAULT_URL: &str = "https://adventofcode.com";
+/// Directory in which downloaded pages are cached, relative to the current directory. Like the
+/// days and the README.md, it is in the workspace root when add-day is run from there.
+const CACHE_DIR: &str = ".aoc-cache";
+/// File in the cache directory containing the time of the last request in nanoseconds since the
+/// unix epoch, so that the interval is also kept between separate runs.
+const LAST_REQUEST_FILE: &str = "last-request";
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether a cached response may be used for a GET request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cache {
+    /// Returns the cached response if there is one and caches the downloaded response otherwise.
+    Use,
+    /// Like `Use`, but only if the cached response is younger than the given age.
+    MaxAge(Duration),
+    /// Downloads the page again and replaces the cached response, e.g. when part two got unlocked.
+    Refresh,
+}
+
+/// HTTP client for the requests to Advent of Code. It sends the session cookie and a User-Agent,
+/// caches responses on disk and waits between requests to not put load on the servers.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    /// Environment variable the session cookie is read from, which depends on the profile.
+    session_var: String,
+    session: Option<String>,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Client {
+    /// Creates a client using the `AOC_SESSION`, `AOC_USER_AGENT` and `AOC_URL` environment variables.
+    /// If a profile is selected, the session cookie is read from `AOC_SESSION_<PROFILE>` instead.
+    pub fn from_env() -> Client {
+        let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let user_agent = var("AOC_USER_AGENT").unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+        let base_url = var("AOC_URL").map_or_else(
+            || DEFAULT_URL.to_string(),
+            |url| url.trim_end_matches('/').to_string(),
+        );
+        // Only the requests to a local server, e.g. the mock server of the tests, aren't throttled.
+        let min_interval = if is_loopback(&base_url) {
+            Duration::ZERO
+        } else {
+            MIN_REQUEST_INTERVAL
+        };
+
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&user_agent)
+            .timeout_connect(CONNECT_TIMEOUT)
+            .timeout(TIMEOUT)
+            .redirects(0)
+            .build();
+        let session_var = session_var(aoc_utils::profile().as_deref());
+        Client {
+            agent,
+            base_url,
+            session: var(&session_var),
+            session_var,
+            cache_dir: PathBuf::from(CACHE_DIR),
+            min_interval,
+        }
+    }
+
+    /// URL of a page of Advent of Code, e.g. `/2024/day/1`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    pub fn has_session(&self) -> bool {
+        self.session.is_some()
+    }
+
+    pub fn session_var(&self) -> &str {
+        &self.session_var
+    }
+
+    /// Fails if there is no session cookie, which is needed for everything that is specific to the user.
+    pub fn require_session(&self) -> Result<()> {
+        if !self.has_session() {
+            return Err(Error::MissingSession(self.session_var.clone()));
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, url: &str, cache: Cache) -> Result<String> {
+        let cache_path = self.cache_dir.join(self.cache_key(url));
+        let age = fs::metadata(&cache_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .map(|modified| modified.elapsed().unwrap_or_default());
+        let use_cache = match (cache, age) {
+            (Cache::Use, Some(_)) => true,
+            (Cache::MaxAge(max_age), Some(age)) => age < max_age,
+            _ => false,
+        };
+        if use_cache {
+            if let Ok(cached) = fs::read_to_string(&cache_path) {
+                println!("Using cached response of {url}");
+                return Ok(cached);
+            }
+        }
+
+        self.throttle()?;
+        let response = self.read_response(url, self.request("GET", url).call())?;
+
+        fs::write(&cache_path, &response)
+            .context(format!("Could not write {}", cache_path.display()))?;
+        Ok(response)
+    }
+
+    /// Sends a form, e.g. an answer. The responses are never cached.
+    pub fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.throttle()?;
+        self.read_response(url, self.request("POST", url).send_form(form))
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        let request = self.agent.request(method, url);
+        match &self.session {
+            Some(session) => request.set("Cookie", &format!("session={session}")),
+            None => request,
+        }
+    }
+
+    fn read_response(
+        &self,
+        url: &str,
+        response: std::result::Result<ureq::Response, ureq::Error>,
+    ) -> Result<String> {
+        let fetch_error = |error: FetchError| Error::Fetch {
+            url: url.to_string(),
+            error,
+        };
+
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, _)) => return Err(fetch_error(status_error(status))),
+            Err(ureq::Error::Transport(transport)) => {
+                let message = transport
+                    .message()
+                    .map_or_else(|| transport.kind().to_string(), String::from);
+                return Err(fetch_error(FetchError::Network(message)));
+            }
+        };
+        if response.status() != 200 {
+            return Err(fetch_error(status_error(response.status())));
+        }
+
+        response
+            .into_string()
+            .map_err(|e| fetch_error(FetchError::Network(e.to_string())))
+    }
+
+    /// Waits until the minimum interval since the last request has passed.
+    fn throttle(&self) -> Result<()> {
+        fs::create_dir_all(&self.cache_dir).context("Could not create cache directory")?;
+        let path = self.cache_dir.join(LAST_REQUEST_FILE);
+
+        let last_request = fs::read_to_string(&path)
+            .ok()
+            .and_then(|nanos| nanos.trim().parse().ok())
+            .map(Duration::from_nanos);
+        let elapsed = last_request.and_then(|last| date::since_epoch().checked_sub(last));
+        if let Some(wait) = elapsed.and_then(|elapsed| self.min_interval.checked_sub(elapsed)) {
+            thread::sleep(wait);
+        }
+
+        let now = date::since_epoch().as_nanos();
+        fs::write(&path, now.to_string()).context(format!("Could not write {}", path.display()))
+    }
+
+    /// Name of the cache file of the URL. Responses depend on the user, e.g. the puzzle input,
+    /// so the session is part of the key.
+    fn cache_key(&self, url: &str) -> String {
+        let path = url.split_once("://").map_or(url, |(_, path)| path);
+        let name: String = path
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        let session = self.session.as_deref().unwrap_or_default();
+        format!("{name}-{:016x}", fnv1a(session.as_bytes()))
+    }
+}
+
+/// Whether the URL points to the local machine, e.g. `http://127.0.0.1:8080` or `http://localhost`.
+fn is_loopback(url: &str) -> bool {
+    let authority = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = authority.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match host_port.strip_prefix('[') {
+        // IPv6 addresses are enclosed in brackets, as they contain colons themselves.
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => host_port.split(':').next().unwrap_or_default(),
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// 64-bit FNV-1a hash. Unlike the hasher of the standard library, it never changes, so the cache
+/// stays valid across Rust releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Name of the environment variable containing the session cookie of the profile, e.g.
+/// `AOC_SESSION_WORK` for the profile `work`. The profile is already validated by
+/// [`aoc_utils::parse_profile`], so only `-` has to be replaced.
+fn session_var(profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => {
+            let suffix = profile.to_ascii_uppercase().replace('-', "_");
+            format!("AOC_SESSION_{suffix}")
+        }
+        None => "AOC_SESSION".to_string(),
+    }
+}
+
+/// Classifies the status of a failed response. Redirects aren't followed, because Advent of Code
+/// redirects to the login page if the session cookie is invalid.
+fn status_error(status: u16) -> FetchError {
+    match status {
+        300..=401 => FetchError::InvalidSession,
+        404 => FetchError::NotUnlocked,
+        429 => FetchError::TooManyRequests,
+        500..=599 => FetchError::ServerError(status),
+        _ => FetchError::UnexpectedStatus(status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn client(cache_dir: PathBuf) -> Client {
+        Client {
+            agent: ureq::agent(),
+            base_url: DEFAULT_URL.to_string(),
+            session_var: "AOC_SESSION".to_string(),
+            session: Some("abc".to_string()),
+            cache_dir,
+            min_interval: Duration::from_millis(200),
+        }
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let client = client(PathBuf::new());
+        let key = client.cache_key("https://adventofcode.com/2024/day/1/input");
+        assert!(key.starts_with("adventofcode_com_2024_day_1_input-"));
+
+        let other_user = Client {
+            session: None,
+            ..client
+        };
+        assert_ne!(
+            other_user.cache_key("https://adventofcode.com/2024/day/1/input"),
+            key
+        );
+    }
+
+    #[test]
+    fn test_is_loopback() {
+        assert!(is_loopback("http://127.0.0.1:43210"));
+        assert!(is_loopback("http://localhost"));
+        assert!(is_loopback("http://[::1]:8080/"));
+        assert!(!is_loopback(DEFAULT_URL));
+        assert!(!is_loopback("http://adventofcode.com"));
+        assert!(!is_loopback("https://www.adventofcode.com"));
+        assert!(!is_loopback("https://localhost.example.com"));
+        assert!(!is_loopback("https://127.0.0.1@adventofcode.com"));
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn test_cached_response() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client(dir.path().to_path_buf());
+        let url = "https://adventofcode.com/2024/day/1";
+        fs::write(dir.path().join(client.cache_key(url)), "cached").unwrap();
+
+        assert_eq!(client.get(url, Cache::Use).unwrap(), "cached");
+        let max_age = Cache::MaxAge(Duration::from_secs(60));
+        assert_eq!(client.get(url, max_age).unwrap(), "cached");
+    }
+
+    #[test]
+    fn test_session_var() {
+        assert_eq!(session_var(None), "AOC_SESSION");
+        assert_eq!(
+            session_var(Some("second-account")),
+            "AOC_SESSION_SECOND_ACCOUNT"
+        );
+    }
+
+    #[test]
+    fn test_status_error() {
+        assert!(matches!(status_error(302), FetchError::InvalidSession));
+        assert!(matches!(status_error(400), FetchError::InvalidSession));
+        assert!(matches!(status_error(404), FetchError::NotUnlocked));
+        assert!(matches!(status_error(429), FetchError::TooManyRequests));
+        assert!(matches!(status_error(503), FetchError::ServerError(503)));
+        assert!(matches!(
+            status_error(418),
+            FetchError::UnexpectedStatus(418)
+        ));
+    }
+
+    #[test]
+    fn test_throttle() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = client(dir.path().to_path_buf());
+
+        let start = Instant::now();
+        client.throttle().unwrap();
+        assert!(start.elapsed() < client.min_interval);
+        client.throttle().unwrap();
+        assert!(start.elapsed() >= client.min_interval);
+    }
+}
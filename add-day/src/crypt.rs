@@ -0,0 +1,121 @@
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::{Error, Result};
+
+/// Why a file would be committed in plaintext.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotEncrypted {
+    /// No rule in `.gitattributes` sets the git-crypt filter for the path.
+    NoRule,
+    /// The key isn't unlocked, so git can't encrypt the file when it is added.
+    Locked,
+}
+
+impl Display for NotEncrypted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotEncrypted::NoRule => write!(f, "no git-crypt rule in .gitattributes matches it"),
+            NotEncrypted::Locked => {
+                write!(f, "the repository is locked, run git-crypt unlock first")
+            }
+        }
+    }
+}
+
+/// Makes sure that a file which has to stay private, i.e. a puzzle input, a puzzle description or
+/// a submission history, would be encrypted when it is committed. Unless `allow_unencrypted` is
+/// set, the file must not be written otherwise, only a warning is shown then.
+pub fn guard(path: &Path, allow_unencrypted: bool) -> Result<()> {
+    let Some(reason) = check(path) else {
+        return Ok(());
+    };
+    if allow_unencrypted {
+        println!(
+            "Warning: {} will not be encrypted, {reason}",
+            path.display()
+        );
+        return Ok(());
+    }
+    Err(Error::NotEncrypted {
+        path: path.to_path_buf(),
+        reason,
+    })
+}
+
+/// Returns why the file would not be encrypted by git-crypt. Outside of a git repository files
+/// can't be committed at all, so they are fine.
+fn check(path: &Path) -> Option<NotEncrypted> {
+    // Linked worktrees have their own git directory, but git-crypt keeps the key in the common one.
+    let git_dir = PathBuf::from(git(&["rev-parse", "--git-common-dir"])?);
+
+    // git can't be asked about a path that isn't valid UTF-8, so it can't be known to be encrypted.
+    let Some(path) = path.to_str() else {
+        return Some(NotEncrypted::NoRule);
+    };
+    let attribute = git(&["check-attr", "filter", "--", path]).unwrap_or_default();
+    if !is_git_crypt_filter(&attribute) {
+        return Some(NotEncrypted::NoRule);
+    }
+    // git-crypt stores the key in the git directory while the repository is unlocked.
+    if !git_dir.join("git-crypt/keys").is_dir() {
+        return Some(NotEncrypted::Locked);
+    }
+    None
+}
+
+/// Reads the output of `git check-attr filter`, e.g. `input_day01.txt: filter: git-crypt`.
+fn is_git_crypt_filter(check_attr: &str) -> bool {
+    check_attr.rsplit(": ").next() == Some("git-crypt")
+}
+
+/// Runs git in the current directory and returns its trimmed output, `None` if it failed.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_git_crypt_filter() {
+        assert!(is_git_crypt_filter(
+            "y2024/day01/input_day01.txt: filter: git-crypt"
+        ));
+        assert!(!is_git_crypt_filter(
+            "y2024/day01/src/main.rs: filter: unspecified"
+        ));
+        assert!(!is_git_crypt_filter(""));
+    }
+
+    #[test]
+    fn test_private_files_of_all_profiles_are_encrypted() {
+        // The tests run in the directory of add-day, which is part of this repository unless the
+        // sources were copied without it.
+        if git(&["rev-parse", "--git-dir"]).is_none() {
+            return;
+        }
+        let files = [
+            "input_day01.txt",
+            "input_day01.work.txt",
+            "PUZZLE.md",
+            "submissions.tsv",
+            "submissions.work.tsv",
+        ];
+        for file in files {
+            let path = format!("../y2024/day01/{file}");
+            assert_ne!(
+                check(Path::new(&path)),
+                Some(NotEncrypted::NoRule),
+                "{file}"
+            );
+        }
+        assert_eq!(check(Path::new("src/main.rs")), Some(NotEncrypted::NoRule));
+    }
+}
@@ -0,0 +1,168 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Offset of the US Eastern time zone in December (EST, UTC-5), in which the puzzles unlock at midnight.
+pub const AOC_UTC_OFFSET_SECONDS: i64 = -5 * 60 * 60;
+
+/// A calendar date in the time zone of Advent of Code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// Number of puzzles of the event, which was shortened to 12 days starting with 2025.
+pub fn event_days(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// Seconds since the unix epoch at which the puzzle of the day unlocks.
+pub fn unlock_time(year: u16, day: u8) -> i64 {
+    Date {
+        year,
+        month: 12,
+        day,
+    }
+    .midnight_unix_seconds()
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> i64 {
+    since_epoch().as_secs() as i64
+}
+
+/// Time since the unix epoch, for when seconds aren't precise enough.
+pub fn since_epoch() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("System time is before 1970")
+}
+
+/// Formats a duration in seconds as `hh:mm:ss`, with the number of days in front if there are any.
+pub fn format_duration(seconds: i64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours >= 24 {
+        format!(
+            "{}d {:02}:{minutes:02}:{seconds:02}",
+            hours / 24,
+            hours % 24
+        )
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+impl Date {
+    /// Returns the current date in the time zone of Advent of Code.
+    pub fn today() -> Date {
+        Date::from_unix_seconds(now() + AOC_UTC_OFFSET_SECONDS)
+    }
+
+    fn from_unix_seconds(seconds: i64) -> Date {
+        // Converts the days since 1970-01-01 into a date of the proleptic Gregorian calendar,
+        // based on http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = seconds.div_euclid(24 * 60 * 60) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Date {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// Seconds since the unix epoch at midnight of this date in the time zone of Advent of Code.
+    pub fn midnight_unix_seconds(&self) -> i64 {
+        // Inverse of from_unix_seconds, based on http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let month = i64::from(self.month);
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month_index = (month + 9) % 12;
+        let day_of_year = (153 * month_index + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era - 719_468;
+
+        days * 24 * 60 * 60 - AOC_UTC_OFFSET_SECONDS
+    }
+
+    /// The day of Advent of Code on this date, if there is one.
+    pub fn advent_day(&self) -> Option<u8> {
+        (self.month == 12 && (1..=event_days(self.year)).contains(&self.day)).then_some(self.day)
+    }
+
+    /// The year of the current event in December, otherwise of the last one.
+    pub fn latest_event_year(&self) -> u16 {
+        if self.month == 12 {
+            self.year
+        } else {
+            self.year - 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_duration, unlock_time, Date};
+
+    fn date(year: u16, month: u8, day: u8) -> Date {
+        Date { year, month, day }
+    }
+
+    #[test]
+    fn test_from_unix_seconds() {
+        assert_eq!(Date::from_unix_seconds(0), date(1970, 1, 1));
+        // 2024-12-01T05:00:00Z, the unlock of the first puzzle of 2024
+        assert_eq!(Date::from_unix_seconds(1_733_029_200), date(2024, 12, 1));
+        // 2024-02-29T12:00:00Z
+        assert_eq!(Date::from_unix_seconds(1_709_208_000), date(2024, 2, 29));
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(unlock_time(2024, 1), 1_733_029_200);
+        assert_eq!(unlock_time(2024, 25) - unlock_time(2024, 24), 24 * 60 * 60);
+        assert_eq!(date(2024, 2, 29).midnight_unix_seconds(), 1_709_182_800);
+        assert_eq!(
+            Date::from_unix_seconds(unlock_time(2023, 5)),
+            date(2023, 12, 5)
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(59), "00:00:59");
+        assert_eq!(format_duration(3 * 3600 + 61), "03:01:01");
+        assert_eq!(format_duration(2 * 86400 + 5), "2d 00:00:05");
+    }
+
+    #[test]
+    fn test_advent_day() {
+        assert_eq!(date(2024, 12, 7).advent_day(), Some(7));
+        assert_eq!(date(2024, 12, 26).advent_day(), None);
+        assert_eq!(date(2025, 12, 13).advent_day(), None);
+        assert_eq!(date(2024, 11, 30).advent_day(), None);
+    }
+
+    #[test]
+    fn test_latest_event_year() {
+        assert_eq!(date(2024, 12, 26).latest_event_year(), 2024);
+        assert_eq!(date(2025, 3, 1).latest_event_year(), 2024);
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::{self, Display};
+use std::io;
+use std::path::PathBuf;
+
+use crate::crypt::NotEncrypted;
+use crate::history::KnownWrong;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Everything that can go wrong while creating a day, reported to the user instead of panicking.
+#[derive(Debug)]
+pub enum Error {
+    /// No day was given and today isn't one of the days of Advent of Code.
+    NoDayToday,
+    /// The crate of the day has to be created before it can be refreshed.
+    DayMissing(PathBuf),
+    UnknownTemplate {
+        name: String,
+        available: Vec<String>,
+    },
+    /// The day00 crate no longer has the title or constants that get replaced in new days.
+    InvalidTemplate(PathBuf),
+    /// The answer is known to be wrong from previous submissions and `--force` wasn't given.
+    KnownWrongAnswer(KnownWrong),
+    /// Downloading inputs and submitting answers requires the session cookie, which is read from
+    /// the environment variable of the profile.
+    MissingSession(String),
+    /// A puzzle input, description or submission history would be committed in plaintext and
+    /// `--allow-unencrypted` wasn't given.
+    NotEncrypted {
+        path: PathBuf,
+        reason: NotEncrypted,
+    },
+    /// Some of the inputs couldn't be downloaded, the reasons are shown in the status table.
+    IncompleteBackfill,
+    /// The answer couldn't be taken from the output of the solution.
+    Solution {
+        package: String,
+        message: String,
+    },
+    Io {
+        context: String,
+        source: io::Error,
+    },
+    Fetch {
+        url: String,
+        error: FetchError,
+    },
+}
+
+/// Why a request to Advent of Code failed.
+#[derive(Debug)]
+pub enum FetchError {
+    /// Advent of Code redirects to the login or responds with 400 if the session cookie is not valid.
+    InvalidSession,
+    /// The puzzle doesn't exist yet.
+    NotUnlocked,
+    ServerError(u16),
+    /// Advent of Code responded with 429, e.g. because everyone requests the puzzle right after the unlock.
+    TooManyRequests,
+    UnexpectedStatus(u16),
+    /// The response couldn't be read, e.g. because the format of an API changed.
+    InvalidResponse(String),
+    /// The request didn't get a response, e.g. because there is no internet connection.
+    Network(String),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::InvalidSession => write!(
+                f,
+                "the session cookie is invalid or expired, log in again and update it in the .env file"
+            ),
+            FetchError::NotUnlocked => write!(
+                f,
+                "the puzzle is not unlocked yet, puzzles unlock at midnight EST (UTC-5)"
+            ),
+            FetchError::ServerError(status) => write!(
+                f,
+                "Advent of Code responded with server error {status}, try again in a few minutes"
+            ),
+            FetchError::TooManyRequests => write!(
+                f,
+                "Advent of Code received too many requests, try again in a few minutes"
+            ),
+            FetchError::UnexpectedStatus(status) => write!(f, "unexpected status {status}"),
+            FetchError::InvalidResponse(message) => write!(f, "unexpected response: {message}"),
+            FetchError::Network(message) => {
+                write!(f, "{message}, check your internet connection")
+            }
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoDayToday => write!(
+                f,
+                "no day given and today is not a day of Advent of Code, please pass the day"
+            ),
+            Error::DayMissing(path) => write!(
+                f,
+                "{} does not exist, create it using the new command first",
+                path.display()
+            ),
+            Error::UnknownTemplate { name, available } => write!(
+                f,
+                "there is no template named {name}, available templates: {}",
+                available.join(", ")
+            ),
+            Error::InvalidTemplate(path) => write!(
+                f,
+                "{} is missing the title comment or the YEAR and DAY constants",
+                path.display()
+            ),
+            Error::KnownWrongAnswer(reason) => write!(
+                f,
+                "not submitting the answer, {reason}. Use --force to submit it anyway"
+            ),
+            Error::MissingSession(var) => write!(
+                f,
+                "no {var} environment variable found, it is needed to download inputs and submit answers. Refer to .env.example"
+            ),
+            Error::NotEncrypted { path, reason } => write!(
+                f,
+                "not writing {}, it would be committed unencrypted because {reason}. Use --allow-unencrypted to write it anyway",
+                path.display()
+            ),
+            Error::IncompleteBackfill => write!(f, "not all inputs could be downloaded"),
+            Error::Solution { package, message } => write!(f, "{package}: {message}"),
+            Error::Io { context, source } => write!(f, "{context}: {source}"),
+            Error::Fetch { url, error } => write!(f, "could not fetch {url}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Adds a description of what was being done to IO errors.
+pub trait IoContext<T> {
+    fn context<S: Into<String>>(self, context: S) -> Result<T>;
+}
+
+impl<T> IoContext<T> for io::Result<T> {
+    fn context<S: Into<String>>(self, context: S) -> Result<T> {
+        self.map_err(|source| Error::Io {
+            context: context.into(),
+            source,
+        })
+    }
+}
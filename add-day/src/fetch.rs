@@ -0,0 +1,43 @@
+use crate::client::{Cache, Client};
+use crate::error::Result;
+use crate::puzzle::Puzzle;
+
+fn puzzle_url(client: &Client, year: u16, day: u8) -> String {
+    client.url(&format!("/{year}/day/{day}"))
+}
+
+/// Downloads the puzzle input of the day. Returns `None` if no session cookie is configured.
+/// The input never changes, so it is usually taken from the cache with `Cache::Use`.
+pub fn fetch_input(client: &Client, year: u16, day: u8, cache: Cache) -> Result<Option<String>> {
+    if !client.has_session() {
+        println!(
+            "No {} environment variable found. Puzzle input will not be automatically fetched. Refer to .env.sample",
+            client.session_var()
+        );
+        return Ok(None);
+    }
+
+    let url = format!("{}/input", puzzle_url(client, year, day));
+    println!("Fetching input from {}...", url);
+    let input = client.get(&url, cache)?;
+    println!("Input fetched successfully!");
+    Ok(Some(input))
+}
+
+/// Downloads the description of the puzzle. Without a session cookie only part one is included,
+/// because part two is only shown to users who solved part one.
+pub fn fetch_puzzle(client: &Client, year: u16, day: u8, cache: Cache) -> Result<Puzzle> {
+    let url = puzzle_url(client, year, day);
+    println!("Fetching puzzle description from {}...", url);
+    let html = client.get(&url, cache)?;
+    Ok(Puzzle::from_html(&html, &url))
+}
+
+/// Submits the answer of a part and returns the response page. Requires the session cookie.
+pub fn post_answer(client: &Client, year: u16, day: u8, level: u8, answer: &str) -> Result<String> {
+    client.require_session()?;
+
+    let url = format!("{}/answer", puzzle_url(client, year, day));
+    println!("Submitting {answer} to {url}...");
+    client.post_form(&url, &[("level", &level.to_string()), ("answer", answer)])
+}
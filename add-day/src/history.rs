@@ -0,0 +1,300 @@
+use std::fmt::{self, Display};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use aoc_utils::{profile_file_name, Answer};
+
+use crate::date;
+use crate::error::{IoContext, Result};
+use crate::submit::{Outcome, Part};
+
+/// An answer that was sent to Advent of Code, together with the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub time: u64,
+    pub part: Part,
+    pub answer: Answer,
+    pub outcome: Outcome,
+}
+
+impl Submission {
+    pub fn new(part: Part, answer: Answer, outcome: Outcome) -> Submission {
+        Submission {
+            time: date::since_epoch().as_secs(),
+            part,
+            answer,
+            outcome,
+        }
+    }
+}
+
+/// Writes the submission as a line of tab separated values: time, part, answer and outcome.
+impl Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.time,
+            self.part,
+            self.answer,
+            self.outcome.key()
+        )
+    }
+}
+
+impl FromStr for Submission {
+    type Err = ();
+
+    fn from_str(line: &str) -> std::result::Result<Self, Self::Err> {
+        let mut fields = line.split('\t');
+        let time = fields.next().ok_or(())?.parse().map_err(|_| ())?;
+        let part = fields.next().ok_or(())?.parse()?;
+        let answer = fields.next().ok_or(())?.parse().unwrap();
+        let outcome = Outcome::from_key(fields.next().ok_or(())?).ok_or(())?;
+        Ok(Submission {
+            time,
+            part,
+            answer,
+            outcome,
+        })
+    }
+}
+
+/// Exclusive bounds of the answer of a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub lower: Option<i128>,
+    pub upper: Option<i128>,
+}
+
+impl Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.lower, self.upper) {
+            (Some(lower), Some(upper)) => write!(f, "{lower} < answer < {upper}"),
+            (Some(lower), None) => write!(f, "answer > {lower}"),
+            (None, Some(upper)) => write!(f, "answer < {upper}"),
+            (None, None) => write!(f, "any answer"),
+        }
+    }
+}
+
+/// Why an answer doesn't need to be submitted to know that it is wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KnownWrong {
+    /// The same answer was submitted before and rejected.
+    Rejected(Outcome),
+    /// Previous answers that were too low or too high rule out the answer.
+    OutOfBounds(Bounds),
+    /// The part was already solved with the given answer.
+    AlreadySolved(Answer),
+}
+
+impl Display for KnownWrong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KnownWrong::Rejected(outcome) => write!(
+                f,
+                "this answer was already submitted and rejected as {}",
+                outcome.key()
+            ),
+            KnownWrong::OutOfBounds(bounds) => {
+                write!(f, "previous submissions showed that {bounds}")
+            }
+            KnownWrong::AlreadySolved(answer) => {
+                write!(f, "the part was already solved with {answer}")
+            }
+        }
+    }
+}
+
+/// The submissions of a day, stored next to its puzzle input.
+pub struct History {
+    path: PathBuf,
+    pub submissions: Vec<Submission>,
+}
+
+impl History {
+    /// Reads the history of the day. Lines that can't be read, e.g. from manual edits, are skipped.
+    pub fn load(day_dir: &Path) -> Result<History> {
+        // The submissions of other profiles are recorded separately, as their answers are different.
+        let path = day_dir.join(profile_file_name("submissions", "tsv"));
+        let submissions = if path.exists() {
+            fs::read_to_string(&path)
+                .context(format!("Could not read {}", path.display()))?
+                .lines()
+                .filter_map(|line| line.parse().ok())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(History { path, submissions })
+    }
+
+    /// File in which the submissions are recorded.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Checks the answer against the previous submissions of the part, so that answers which are
+    /// known to be wrong don't cause another lockout.
+    pub fn check(&self, part: Part, answer: &Answer) -> Option<KnownWrong> {
+        let submissions = || self.submissions.iter().filter(|s| s.part == part);
+
+        if let Some(correct) = submissions().find(|s| s.outcome == Outcome::Correct) {
+            return Some(KnownWrong::AlreadySolved(correct.answer.clone()));
+        }
+
+        if let Some(rejected) = submissions().find(|s| s.answer == *answer && s.outcome.is_wrong())
+        {
+            return Some(KnownWrong::Rejected(rejected.outcome.clone()));
+        }
+
+        let Answer::Number(n) = answer else {
+            return None;
+        };
+        let bounds = self.bounds(part);
+        match (bounds.lower, bounds.upper) {
+            (Some(lower), _) if *n <= lower => Some(KnownWrong::OutOfBounds(bounds)),
+            (_, Some(upper)) if *n >= upper => Some(KnownWrong::OutOfBounds(bounds)),
+            _ => None,
+        }
+    }
+
+    /// The range in which the answer has to be, learned from answers that were too low or too high.
+    pub fn bounds(&self, part: Part) -> Bounds {
+        let numbers = |outcome: Outcome| {
+            self.submissions
+                .iter()
+                .filter(move |s| s.part == part && s.outcome == outcome)
+                .filter_map(|s| match s.answer {
+                    Answer::Number(n) => Some(n),
+                    Answer::Text(_) => None,
+                })
+        };
+
+        Bounds {
+            lower: numbers(Outcome::TooLow).max(),
+            upper: numbers(Outcome::TooHigh).min(),
+        }
+    }
+
+    pub fn record(&mut self, submission: Submission) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context(format!("Could not open {}", self.path.display()))?;
+        writeln!(file, "{submission}")
+            .context(format!("Could not write {}", self.path.display()))?;
+
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_line_roundtrip() {
+        let submission = Submission {
+            time: 1733029200,
+            part: Part::B,
+            answer: Answer::from("1,2,3"),
+            outcome: Outcome::RateLimited(Some(Duration::from_secs(60))),
+        };
+        let line = submission.to_string();
+        assert_eq!(line, "1733029200\tB\t1,2,3\trate-limited");
+        assert_eq!(
+            line.parse::<Submission>().unwrap(),
+            Submission {
+                outcome: Outcome::RateLimited(None),
+                ..submission
+            }
+        );
+        assert!("1733029200\tC\t12\tcorrect".parse::<Submission>().is_err());
+    }
+
+    fn history(submissions: &[(Part, Answer, Outcome)]) -> History {
+        History {
+            path: PathBuf::new(),
+            submissions: submissions
+                .iter()
+                .map(|(part, answer, outcome)| Submission {
+                    time: 0,
+                    part: *part,
+                    answer: answer.clone(),
+                    outcome: outcome.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let history = history(&[
+            (Part::A, Answer::Number(100), Outcome::TooLow),
+            (Part::A, Answer::Number(120), Outcome::TooLow),
+            (Part::A, Answer::Number(500), Outcome::TooHigh),
+            (Part::A, Answer::Number(300), Outcome::Incorrect),
+            (Part::A, Answer::Number(200), Outcome::RateLimited(None)),
+            (Part::B, Answer::from("abc"), Outcome::Incorrect),
+        ]);
+        let bounds = Bounds {
+            lower: Some(120),
+            upper: Some(500),
+        };
+
+        assert_eq!(history.bounds(Part::A), bounds);
+        assert_eq!(
+            history.check(Part::A, &Answer::Number(110)),
+            Some(KnownWrong::OutOfBounds(bounds))
+        );
+        assert_eq!(
+            history.check(Part::A, &Answer::Number(500)),
+            Some(KnownWrong::Rejected(Outcome::TooHigh))
+        );
+        assert_eq!(
+            history.check(Part::A, &Answer::Number(300)),
+            Some(KnownWrong::Rejected(Outcome::Incorrect))
+        );
+        assert_eq!(history.check(Part::A, &Answer::Number(200)), None);
+        assert_eq!(history.check(Part::B, &Answer::Number(110)), None);
+        assert_eq!(
+            history.check(Part::B, &Answer::from("abc")),
+            Some(KnownWrong::Rejected(Outcome::Incorrect))
+        );
+    }
+
+    #[test]
+    fn test_check_solved() {
+        let history = history(&[(Part::A, Answer::Number(7), Outcome::Correct)]);
+        assert_eq!(
+            history.check(Part::A, &Answer::Number(8)),
+            Some(KnownWrong::AlreadySolved(Answer::Number(7)))
+        );
+        assert_eq!(history.check(Part::B, &Answer::Number(8)), None);
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(dir.path()).unwrap();
+        assert!(history.submissions.is_empty());
+
+        let submission = Submission::new(Part::A, Answer::Number(42), Outcome::TooLow);
+        history.record(submission.clone()).unwrap();
+        history.record(submission.clone()).unwrap();
+
+        assert_eq!(
+            History::load(dir.path()).unwrap().submissions,
+            vec![submission.clone(), submission]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+/// A piece of an HTML document, as far as it is needed to read the puzzle pages of Advent of Code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Start {
+        name: String,
+        attributes: String,
+    },
+    End(String),
+    /// Text with its entities decoded.
+    Text(String),
+}
+
+impl Token {
+    pub fn is_start(&self, tag: &str) -> bool {
+        matches!(self, Token::Start { name, .. } if name == tag)
+    }
+
+    pub fn is_end(&self, tag: &str) -> bool {
+        matches!(self, Token::End(name) if name == tag)
+    }
+
+    /// Returns the value of an attribute of a start tag, e.g. the `href` of a link.
+    pub fn attribute(&self, attribute: &str) -> Option<&str> {
+        let Token::Start { attributes, .. } = self else {
+            return None;
+        };
+
+        let prefix = format!("{attribute}=\"");
+        let (start, _) = attributes
+            .match_indices(&prefix)
+            .find(|(i, _)| *i == 0 || attributes[..*i].ends_with(char::is_whitespace))?;
+        let value = &attributes[start + prefix.len()..];
+        value.split_once('"').map(|(value, _)| value)
+    }
+}
+
+/// Splits the document into tags and text. Comments and the content of scripts and styles are skipped.
+///
+/// This is no complete HTML parser, it only handles the well-formed markup of the puzzle pages.
+pub fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+            continue;
+        }
+
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+            continue;
+        }
+
+        let Some((tag, after)) = rest[1..].split_once('>') else {
+            // An unclosed tag at the end of the document.
+            break;
+        };
+        rest = after;
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::End(name.trim().to_ascii_lowercase()));
+            continue;
+        }
+
+        let tag = tag.trim_end_matches('/');
+        let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        let name = name.to_ascii_lowercase();
+        if name.starts_with('!') {
+            // <!DOCTYPE html>
+            continue;
+        }
+
+        if name == "script" || name == "style" {
+            let end = format!("</{name}>");
+            rest = rest.split_once(&end).map_or("", |(_, after)| after);
+            continue;
+        }
+
+        tokens.push(Token::Start {
+            name,
+            attributes: attributes.trim().to_string(),
+        });
+    }
+
+    tokens
+}
+
+/// Returns the tokens inside of each `<article>` of the document, without the article tags.
+pub fn articles(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut articles = Vec::new();
+    let mut start = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_start("article") {
+            start = Some(i + 1);
+        } else if token.is_end("article") {
+            if let Some(start) = start.take() {
+                articles.push(&tokens[start..i]);
+            }
+        }
+    }
+
+    articles
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..]
+            .split_once(';')
+            .and_then(|(entity, _)| decode_entity(entity).map(|c| (entity.len(), c)));
+        match entity {
+            Some((len, c)) => {
+                decoded.push(c);
+                rest = &rest[len + 2..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let code = entity.strip_prefix('#')?;
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(name: &str, attributes: &str) -> Token {
+        Token::Start {
+            name: name.to_string(),
+            attributes: attributes.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize(
+            "<!DOCTYPE html><!-- hi --><p class=\"x\">a &lt;b&gt; &amp; &#39;c&#x27;<br/></p><script>if (a<b) {}</script>",
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                start("p", "class=\"x\""),
+                Token::Text("a <b> & 'c'".to_string()),
+                start("br", ""),
+                Token::End("p".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attribute() {
+        let link = start(
+            "a",
+            "data-href=\"/no\" href=\"/2024/day/1/input\" target=\"_blank\"",
+        );
+        assert_eq!(link.attribute("href"), Some("/2024/day/1/input"));
+        assert_eq!(link.attribute("title"), None);
+    }
+
+    #[test]
+    fn test_articles() {
+        let tokens =
+            tokenize("<main><article><p>one</p></article><p>x</p><article>two</article></main>");
+        let articles = articles(&tokens);
+        assert_eq!(articles.len(), 2);
+        assert_eq!(articles[1], &[Token::Text("two".to_string())]);
+    }
+}
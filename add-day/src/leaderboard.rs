@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::client::{Cache, Client};
+use crate::date;
+use crate::error::{Error, FetchError, Result};
+
+/// Advent of Code asks to not request the leaderboards more often than every 15 minutes.
+const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// A private leaderboard, as returned by its JSON API.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Members that don't share their name are anonymous.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// The stars of each part of each day, keyed by day and part number.
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Seconds since the unix epoch at which the member got the star of the part, if at all.
+    pub fn star_time(&self, day: u8, part: u8) -> Option<i64> {
+        let parts = self.completion_day_level.get(&day.to_string())?;
+        parts.get(&part.to_string()).map(|star| star.get_star_ts)
+    }
+
+    fn stars_of_day(&self, day: u8) -> usize {
+        (1..=2)
+            .filter(|part| self.star_time(day, *part).is_some())
+            .count()
+    }
+}
+
+impl Leaderboard {
+    /// Members sorted by their local score, the ones with more stars first on a tie.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| (b.local_score, b.stars, a.id).cmp(&(a.local_score, a.stars, b.id)));
+        members
+    }
+
+    /// The last day on which any member got a star.
+    pub fn latest_day(&self) -> Option<u8> {
+        self.members
+            .values()
+            .flat_map(|member| member.completion_day_level.keys())
+            .filter_map(|day| day.parse().ok())
+            .max()
+    }
+
+    /// Renders the score, stars and a row of the stars of each day for every member.
+    /// `*` marks days with both stars and `.` days with only the first one.
+    pub fn render(&self, days: u8) -> String {
+        let ranking = self.ranking();
+        let name_width = name_width(&ranking);
+
+        let mut out = String::new();
+        let day_header: String = (1..=days).map(|day| (b'0' + day % 10) as char).collect();
+        writeln!(
+            out,
+            "{:>4} {:>5} {:>5}  {:<name_width$}  {day_header}",
+            "", "Score", "Stars", "Name"
+        )
+        .unwrap();
+        for (rank, member) in ranking.iter().enumerate() {
+            let calendar: String = (1..=days)
+                .map(|day| match member.stars_of_day(day) {
+                    2 => '*',
+                    1 => '.',
+                    _ => ' ',
+                })
+                .collect();
+            writeln!(
+                out,
+                "{:>4} {:>5} {:>5}  {:<name_width$}  {calendar}",
+                format!("{})", rank + 1),
+                member.local_score,
+                member.stars,
+                member.display_name()
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    /// Renders how long each member took for both parts of the day after it unlocked.
+    pub fn render_day(&self, year: u16, day: u8) -> String {
+        let unlock = date::unlock_time(year, day);
+        let ranking = self.ranking();
+        let name_width = name_width(&ranking);
+        let time = |member: &Member, part| {
+            member.star_time(day, part).map_or_else(
+                || "-".to_string(),
+                |time| date::format_duration(time - unlock),
+            )
+        };
+
+        let mut out = String::new();
+        writeln!(out, "Completion times of day {day}:").unwrap();
+        writeln!(
+            out,
+            "{:<name_width$}  {:>12}  {:>12}",
+            "Name", "Part 1", "Part 2"
+        )
+        .unwrap();
+        for member in ranking.iter().filter(|member| member.stars_of_day(day) > 0) {
+            writeln!(
+                out,
+                "{:<name_width$}  {:>12}  {:>12}",
+                member.display_name(),
+                time(member, 1),
+                time(member, 2)
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+/// Downloads the leaderboard, or takes it from the cache if it was downloaded in the last 15 minutes.
+pub fn fetch_leaderboard(client: &Client, year: u16, id: u64) -> Result<Leaderboard> {
+    client.require_session()?;
+
+    let url = client.url(&format!("/{year}/leaderboard/private/view/{id}.json"));
+    let json = client.get(&url, Cache::MaxAge(LEADERBOARD_MAX_AGE))?;
+    parse(&json).map_err(|message| Error::Fetch {
+        url,
+        error: FetchError::InvalidResponse(message),
+    })
+}
+
+fn parse(json: &str) -> std::result::Result<Leaderboard, String> {
+    serde_json::from_str(json).map_err(|err| err.to_string())
+}
+
+fn name_width(members: &[&Member]) -> usize {
+    members
+        .iter()
+        .map(|member| member.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../fixtures/leaderboard.json");
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = parse(FIXTURE).unwrap();
+        assert_eq!(leaderboard.event, "2024");
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(leaderboard.latest_day(), Some(2));
+
+        let names: Vec<String> = leaderboard
+            .ranking()
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        assert_eq!(names, ["Alice", "Bob", "(anonymous user #1003)"]);
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard = parse(FIXTURE).unwrap();
+        let rendered = leaderboard.render(3);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "     Score Stars  Name                    123");
+        assert_eq!(lines[1], "  1)    17     4  Alice                   ** ");
+        assert_eq!(lines[2], "  2)    13     3  Bob                     *. ");
+        assert_eq!(lines[3], "  3)     1     1  (anonymous user #1003)  .  ");
+    }
+
+    #[test]
+    fn test_render_day() {
+        let leaderboard = parse(FIXTURE).unwrap();
+        let rendered = leaderboard.render_day(2024, 1);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(
+            lines[2],
+            "Alice                       00:05:00      00:10:00"
+        );
+        assert_eq!(
+            lines[3],
+            "Bob                         00:03:20      01:03:20"
+        );
+        assert_eq!(
+            lines[4],
+            "(anonymous user #1003)   2d 04:00:00             -"
+        );
+    }
+}
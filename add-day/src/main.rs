@@ -1,144 +1,547 @@
 use std::fs;
-use std::io::{self, Write};
-use std::path::Path;
-use std::path::PathBuf;
+use std::process::ExitCode;
 
+use aoc_utils::{day_dir, input_file_name, parse_profile, PROFILE_VAR};
+use clap::{Args, Parser, Subcommand};
 use dotenvy::dotenv;
 
-const AOC_YEAR: &str = "2024";
+mod auth;
+mod backfill;
+mod calendar;
+mod client;
+mod crypt;
+mod date;
+mod error;
+mod fetch;
+mod history;
+mod html;
+mod leaderboard;
+mod puzzle;
+mod scaffold;
+mod submit;
+mod template;
+mod wait;
 
-// This is a utility that asks the user for a input number
-// and then creates a new crate for the advent of code challenge of that day.
+use backfill::InputStatus;
+use client::{Cache, Client};
+use date::Date;
+use error::{Error, IoContext, Result};
+use history::{History, Submission};
+use puzzle::Puzzle;
+use scaffold::{Action, Plan};
+use submit::{Outcome, Part};
+use template::TemplateValues;
+
+const README_PATH: &str = "README.md";
+
+// This is a utility that creates a new crate for the advent of code challenge of a day.
 // Additionally it will download the puzzle input for that day and store it in the
 // newly created crate, if the session cookie is provided in the AOC_SESSION env variable or in the .env file.
 
-fn main() {
+/// Creates crates for the days of Advent of Code and downloads their puzzle inputs.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Profile of the Advent of Code account to use. Its session cookie is read from
+    /// AOC_SESSION_<PROFILE> and its inputs are stored in input_dayNN.<profile>.txt.
+    /// Letters, digits, '_' and '-' are allowed, and the name is converted to lowercase.
+    #[arg(long, global = true, env = PROFILE_VAR, value_parser = parse_profile)]
+    profile: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates the crate for a day and downloads its puzzle input and description.
+    New(NewArgs),
+    /// Downloads the puzzle description of a day again, e.g. to get part two after solving part one.
+    Refresh(RefreshArgs),
+    /// Submits the answer of a part and records the response in the submissions.tsv of the day.
+    Submit(SubmitArgs),
+    /// Downloads missing or empty puzzle inputs of existing days.
+    Fetch(FetchArgs),
+    /// Shows the members, stars, scores and completion times of a private leaderboard.
+    Leaderboard(LeaderboardArgs),
+    /// Shows a calendar of the days with a crate, an input, verified answers and stars.
+    Progress(ProgressArgs),
+    /// Checks the session cookie of the profile.
+    #[command(subcommand)]
+    Auth(AuthCommand),
+}
+
+#[derive(Subcommand)]
+enum AuthCommand {
+    /// Verifies that the session cookie is valid and shows the user it belongs to.
+    Check {
+        /// Year of the event whose stars are shown. Defaults to the current or last event.
+        #[arg(long, env = "AOC_YEAR")]
+        year: Option<u16>,
+    },
+}
+
+#[derive(Args)]
+struct DayArgs {
+    /// Day of the puzzle. Defaults to today during Advent of Code.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// Year of the event. Defaults to the current or last event.
+    #[arg(long, env = "AOC_YEAR")]
+    year: Option<u16>,
+}
+
+impl DayArgs {
+    /// Returns the year and day, filling in the defaults based on today's date.
+    fn resolve(&self) -> Result<(u16, u8)> {
+        let today = Date::today();
+        let year = self.year.unwrap_or_else(|| today.latest_event_year());
+        let day = self.day.or(today.advent_day()).ok_or(Error::NoDayToday)?;
+        Ok((year, day))
+    }
+}
+
+#[derive(Args)]
+struct NewArgs {
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// Creates an empty input file and no PUZZLE.md instead of downloading them.
+    #[arg(long)]
+    no_fetch: bool,
+
+    /// Overwrites the existing files of the day, by default only missing files are created.
+    #[arg(long)]
+    force: bool,
+
+    /// Shows which files would be created or overwritten, without downloading or writing anything.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Template used for the main.rs: day00 (a copy of the day00 crate, also named lines) or one in add-day/templates, e.g. grid or two-section.
+    #[arg(short, long, default_value = template::DEFAULT_TEMPLATE)]
+    template: String,
+
+    /// Title of the puzzle that is put at the top of the main.rs. Defaults to "Day <day>".
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Creates the crate right away and downloads the input and puzzle once the day is unlocked.
+    #[arg(long, conflicts_with = "no_fetch")]
+    wait: bool,
+
+    /// Writes the input and PUZZLE.md even if git-crypt wouldn't encrypt them, e.g. in a fork
+    /// without git-crypt.
+    #[arg(long)]
+    allow_unencrypted: bool,
+}
+
+#[derive(Args)]
+struct RefreshArgs {
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// Writes the PUZZLE.md even if git-crypt wouldn't encrypt it, e.g. in a fork without git-crypt.
+    #[arg(long)]
+    allow_unencrypted: bool,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    /// Day of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// Part to submit the answer for: a or b.
+    part: Part,
+
+    /// Answer to submit. Defaults to the answer printed by the solution of the day.
+    answer: Option<String>,
+
+    /// Year of the event. Defaults to the current or last event.
+    #[arg(long, env = "AOC_YEAR")]
+    year: Option<u16>,
+
+    /// Submits the answer even if previous submissions show that it is wrong.
+    #[arg(long)]
+    force: bool,
+
+    /// Records the submission even if git-crypt wouldn't encrypt the history, e.g. in a fork
+    /// without git-crypt.
+    #[arg(long)]
+    allow_unencrypted: bool,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    /// Day whose input is downloaded. Defaults to today during Advent of Code.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// Downloads the inputs of all unlocked days, of every year in the workspace unless --year is given.
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+
+    /// Year of the event. Defaults to the current or last event for a single day.
+    #[arg(long, env = "AOC_YEAR")]
+    year: Option<u16>,
+
+    /// Downloads the inputs again even if they are present.
+    #[arg(long)]
+    force: bool,
+
+    /// Writes the inputs even if git-crypt wouldn't encrypt them, e.g. in a fork without git-crypt.
+    #[arg(long)]
+    allow_unencrypted: bool,
+}
+
+#[derive(Args)]
+struct LeaderboardArgs {
+    /// Id of the private leaderboard, which is the number at the end of its URL.
+    #[arg(long, env = "AOC_LEADERBOARD_ID")]
+    id: u64,
+
+    /// Year of the event. Defaults to the current or last event.
+    #[arg(long, env = "AOC_YEAR")]
+    year: Option<u16>,
+
+    /// Day whose completion times are shown. Defaults to the last day anyone got a star on.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+}
+
+#[derive(Args)]
+struct ProgressArgs {
+    /// Year of the event. Defaults to the current or last event.
+    #[arg(long, env = "AOC_YEAR")]
+    year: Option<u16>,
+
+    /// Takes the stars from the calendar of the event on Advent of Code instead of only from the
+    /// submission history. Requires the session cookie.
+    #[arg(long)]
+    online: bool,
+
+    /// Updates the progress table of the year in the README.md.
+    #[arg(long)]
+    readme: bool,
+}
+
+fn main() -> ExitCode {
     dotenv().ok();
+    let cli = Cli::parse();
+    // The solutions read the input of the profile from the environment as well, e.g. when they
+    // are run by submit.
+    if let Some(profile) = &cli.profile {
+        std::env::set_var(PROFILE_VAR, profile);
+    }
+
+    let client = Client::from_env();
 
-    let day = get_day();
-    let day_dir = create_day_dir(day);
-    create_cargo_toml(day, &day_dir);
-    create_src(day, &day_dir);
-    create_input_file(day, &day_dir);
+    let result = match cli.command {
+        Command::New(args) => new_day(&client, args),
+        Command::Refresh(args) => refresh_day(&client, args),
+        Command::Submit(args) => submit_answer(&client, args),
+        Command::Fetch(args) => fetch_inputs(&client, args),
+        Command::Leaderboard(args) => show_leaderboard(&client, args),
+        Command::Progress(args) => show_progress(&client, args),
+        Command::Auth(AuthCommand::Check { year }) => check_auth(&client, year),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
 }
 
-fn get_day() -> u8 {
-    print!("Please enter the day you want to create a crate for: ");
-    io::stdout().flush().unwrap();
+fn new_day(client: &Client, args: NewArgs) -> Result<()> {
+    let (year, day) = args.day.resolve()?;
+    // Load the template first, so that a typo in its name doesn't leave a half created day behind.
+    template::load(&args.template)?;
+    let title = args.title.unwrap_or_else(|| format!("Day {day}"));
+
+    let plan = Plan::new(day_dir(year, day), day, args.force, !args.no_fetch);
+    if args.dry_run {
+        print!("{plan}");
+        return Ok(());
+    }
+    if plan.is_complete() {
+        println!(
+            "{} is already complete, use --force to overwrite it",
+            plan.day_dir.display()
+        );
+        return Ok(());
+    }
+
+    // Inputs and puzzle descriptions must never end up in the repository in plaintext, so this is
+    // checked before anything is downloaded or written.
+    if plan.needs_input() && client.has_session() {
+        let input_path = plan.day_dir.join(input_file_name(day));
+        crypt::guard(&input_path, args.allow_unencrypted)?;
+    }
+    if plan.puzzle.is_some_and(Action::writes) {
+        let puzzle_path = plan.day_dir.join(scaffold::PUZZLE_FILE);
+        crypt::guard(&puzzle_path, args.allow_unencrypted)?;
+    }
+
+    // Everything is downloaded before any file is written, so failed requests don't leave a
+    // half created day behind and the command can simply be run again.
+    // When waiting for the unlock, the crate is created first and completed after the download.
+    let (puzzle, input) = if args.wait {
+        (None, None)
+    } else {
+        download(client, year, day, &plan)?
+    };
 
-    let mut day = String::new();
-    io::stdin()
-        .read_line(&mut day)
-        .expect("Failed to read line");
-    day.trim().parse::<u8>().expect("Please enter a number!")
+    let values = template_values(year, day, &title, puzzle.as_ref());
+    let day_dir = &plan.day_dir;
+    let create_files = || -> Result<()> {
+        scaffold::create_day_dir(day_dir)?;
+        if plan.cargo_toml.writes() {
+            scaffold::create_cargo_toml(year, day, day_dir)?;
+        }
+        if plan.main_rs.writes() {
+            scaffold::create_src(day_dir, &args.template, &values)?;
+        }
+        // While waiting, a missing input is created empty and filled in after the download.
+        if input.is_some() || plan.input == Action::Create {
+            scaffold::create_input_file(day, day_dir, input.as_deref())?;
+        }
+        if let (Some(puzzle), Some(action)) = (&puzzle, plan.puzzle) {
+            if action.writes() {
+                scaffold::create_puzzle_file(day_dir, puzzle)?;
+            }
+        }
+        Ok(())
+    };
+
+    let existed = day_dir.exists();
+    if let Err(err) = create_files() {
+        if !existed && fs::remove_dir_all(day_dir).is_ok() {
+            println!("Removed the partially created {}", day_dir.display());
+        }
+        return Err(err);
+    }
+    print!("{plan}");
+
+    if args.wait {
+        wait::wait_for_unlock(year, day);
+        let (puzzle, input) = wait::with_retries(|| download(client, year, day, &plan))?;
+
+        if input.is_some() {
+            scaffold::create_input_file(day, day_dir, input.as_deref())?;
+        }
+        if let Some(puzzle) = &puzzle {
+            if plan.puzzle.is_some_and(Action::writes) {
+                scaffold::create_puzzle_file(day_dir, puzzle)?;
+            }
+        }
+        let downloaded_values = template_values(year, day, &title, puzzle.as_ref());
+        if plan.main_rs.writes()
+            && !scaffold::update_src(day_dir, &args.template, &values, &downloaded_values)?
+        {
+            println!("main.rs was changed while waiting, the examples are not added to its tests");
+        }
+        println!("Downloaded the input and puzzle description of day {day}");
+    }
+    Ok(())
 }
 
-fn create_day_dir(day: u8) -> PathBuf {
-    let day_directory_str = format!("day{:0>2}", day);
-    let day_directory = PathBuf::from(&day_directory_str);
-    fs::create_dir(&day_directory).expect("Could not create day directory");
+/// Downloads the description and the input of the puzzle, as far as they are needed for the plan.
+fn download(
+    client: &Client,
+    year: u16,
+    day: u8,
+    plan: &Plan,
+) -> Result<(Option<Puzzle>, Option<String>)> {
+    let puzzle = if plan.needs_puzzle() {
+        Some(fetch::fetch_puzzle(client, year, day, Cache::Use)?)
+    } else {
+        None
+    };
+    let input = if plan.needs_input() {
+        fetch::fetch_input(client, year, day, Cache::Use)?
+    } else {
+        None
+    };
+
+    if plan.main_rs.writes() && puzzle.as_ref().is_some_and(|p| p.examples().0.is_none()) {
+        println!("No example input found in the puzzle description, TEST_INPUT is left empty");
+    }
+    Ok((puzzle, input))
+}
 
-    day_directory
+fn template_values(year: u16, day: u8, title: &str, puzzle: Option<&Puzzle>) -> TemplateValues {
+    let (example, answers) = puzzle.map(Puzzle::examples).unwrap_or_default();
+    TemplateValues {
+        year,
+        day,
+        title: title.to_string(),
+        example: example.map(String::from),
+        answers: answers.map(|answer| answer.cloned()),
+    }
 }
 
-fn create_cargo_toml(day: u8, day_dir: &Path) {
-    let cargo_toml_path = day_dir.join("Cargo.toml");
-    let cargo_toml_str = format!(
-        r#"[package]
-name = "day{:02}"
-version = "0.1.0"
-edition = "2021"
+fn refresh_day(client: &Client, args: RefreshArgs) -> Result<()> {
+    let (year, day) = args.day.resolve()?;
+    let day_dir = day_dir(year, day);
+    if !day_dir.exists() {
+        return Err(Error::DayMissing(day_dir));
+    }
+    crypt::guard(&day_dir.join(scaffold::PUZZLE_FILE), args.allow_unencrypted)?;
 
-[dependencies]
-aoc-utils = {{ path = "../aoc-utils" }}
-"#,
-        day
-    );
-    fs::write(cargo_toml_path, cargo_toml_str).expect("Could not write Cargo.toml");
-}
-
-fn create_src(day: u8, day_dir: &Path) {
-    let src_dir = &day_dir.join("src");
-    fs::create_dir(src_dir).expect("Could not create src directory");
-
-    let main_rs_path = src_dir.join("main.rs");
-    let main_rs_str = format!(
-        r#"use aoc_utils::PuzzleInput;
-const DAY: u8 = {day};
-
-fn main() {{
-    let input = PuzzleInput::get_input(DAY);
-    println!("A: {{}}", solve_a(&input));
-    println!("B: {{}}", solve_b(&input));
-}}
-
-fn solve_a(input: &PuzzleInput) -> usize {{
-    input.lines().count()
-}}
-
-fn solve_b(input: &PuzzleInput) -> usize {{
-    input.lines().count()
-}}
-
-#[cfg(test)]
-mod tests {{
-    use super::*;
-
-    const TEST_INPUT: &str = "";
-
-    #[test]
-    fn test_no_panic() {{
-        let input = PuzzleInput::get_input(DAY);
-        solve_a(&input);
-        solve_b(&input);
-    }}
-
-    #[test]
-    fn test_solve_a() {{
-        assert_eq!(solve_a(&PuzzleInput::new(TEST_INPUT)), 0);
-    }}
-
-    #[test]
-    fn test_solve_b() {{
-        assert_eq!(solve_b(&PuzzleInput::new(TEST_INPUT)), 0);
-    }}
-}}
-"#,
-        day = day
-    );
-    fs::write(main_rs_path, main_rs_str).expect("Could not write main.rs");
+    let puzzle = fetch::fetch_puzzle(client, year, day, Cache::Refresh)?;
+    scaffold::create_puzzle_file(&day_dir, &puzzle)?;
+    println!("Updated {}/PUZZLE.md", day_dir.display());
+
+    // The tests are not touched anymore, as they might have been changed while solving part one.
+    match puzzle.parts.get(1) {
+        None => println!("Part two is not unlocked yet"),
+        Some(part_two) => {
+            if let Some(example) = &part_two.example {
+                println!("Example of part two:\n{example}");
+            }
+            if let Some(answer) = &part_two.example_answer {
+                println!("Example answer of part two: {answer}");
+            }
+        }
+    }
+    Ok(())
 }
 
-fn create_input_file(day: u8, day_dir: &Path) {
-    let name = format!("input_day{:02}.txt", day);
-    let input_file_path = day_dir.join(name);
-    let mut file = fs::File::create(input_file_path).expect("Could not create input file");
+fn submit_answer(client: &Client, args: SubmitArgs) -> Result<()> {
+    let (year, day) = DayArgs {
+        day: Some(args.day),
+        year: args.year,
+    }
+    .resolve()?;
+    let day_dir = day_dir(year, day);
+    if !day_dir.exists() {
+        return Err(Error::DayMissing(day_dir));
+    }
+    // Checked before running the solution, which can take a while, and before submitting, so that
+    // the answer can be recorded afterwards.
+    client.require_session()?;
+    let mut history = History::load(&day_dir)?;
+    crypt::guard(history.path(), args.allow_unencrypted)?;
+
+    let answer = match args.answer {
+        Some(answer) => answer.parse().unwrap(),
+        None => submit::solution_answer(year, day, args.part)?,
+    };
+    if let Some(reason) = history.check(args.part, &answer) {
+        if !args.force {
+            return Err(Error::KnownWrongAnswer(reason));
+        }
+        println!("Submitting anyway, {reason}");
+    }
+
+    let response = fetch::post_answer(client, year, day, args.part.level(), &answer.to_string())?;
+    let outcome = Outcome::from_response(&response);
+    println!("{outcome}");
 
-    let input_content = fetch_input(day);
-    let input_content = input_content.as_bytes();
-    file.write_all(input_content)
-        .expect("Could not write input file");
+    history.record(Submission::new(args.part, answer, outcome))
 }
 
-fn fetch_input(day: u8) -> String {
-    let session = std::env::var("AOC_SESSION").ok();
-    if session.is_none() {
-        println!("No AOC_SESSION environment variable found. Puzzle input will not be automatically fetched. Refer to .env.sample");
-        return String::new();
+fn fetch_inputs(client: &Client, args: FetchArgs) -> Result<()> {
+    client.require_session()?;
+
+    let days: Vec<(u16, u8)> = if args.all {
+        let years = match args.year {
+            Some(year) => vec![year],
+            None => backfill::find_years()?,
+        };
+        years
+            .into_iter()
+            .flat_map(|year| backfill::unlocked_days(year).map(move |day| (year, day)))
+            .collect()
+    } else {
+        let day_args = DayArgs {
+            day: args.day,
+            year: args.year,
+        };
+        vec![day_args.resolve()?]
+    };
+
+    let mut statuses = Vec::new();
+    for (year, day) in days {
+        let status =
+            backfill::backfill_input(client, year, day, args.force, args.allow_unencrypted);
+        let stop = status.stops_backfill();
+        statuses.push((year, day, status));
+        if stop {
+            break;
+        }
     }
-    let session = session.unwrap();
 
-    let url = format!("https://adventofcode.com/{AOC_YEAR}/day/{day}/input");
-    println!("Fetching input from {}...", url);
-    let response = ureq::get(&url)
-        .set("Cookie", &format!("session={}", session))
-        .call()
-        .expect("Could not fetch input");
+    println!();
+    println!("{:<12} Input", "Day");
+    for (year, day, status) in &statuses {
+        println!("{:<12} {status}", format!("{year} day {day:02}"));
+    }
 
-    if response.status() != 200 {
-        panic!("Could not fetch input");
+    if statuses
+        .iter()
+        .any(|(_, _, status)| matches!(status, InputStatus::Failed(_)))
+    {
+        return Err(Error::IncompleteBackfill);
     }
+    Ok(())
+}
+
+fn show_leaderboard(client: &Client, args: LeaderboardArgs) -> Result<()> {
+    let year = args
+        .year
+        .unwrap_or_else(|| Date::today().latest_event_year());
+    let leaderboard = leaderboard::fetch_leaderboard(client, year, args.id)?;
 
-    println!("Input fetched successfully!");
-    response.into_string().expect("Could not parse input")
+    println!("\nPrivate leaderboard {} of {}", args.id, leaderboard.event);
+    print!("{}", leaderboard.render(date::event_days(year)));
+    if let Some(day) = args.day.or(leaderboard.latest_day()) {
+        println!();
+        print!("{}", leaderboard.render_day(year, day));
+    }
+    Ok(())
+}
+
+fn show_progress(client: &Client, args: ProgressArgs) -> Result<()> {
+    let year = args
+        .year
+        .unwrap_or_else(|| Date::today().latest_event_year());
+    let calendar = if args.online {
+        client.require_session()?;
+        Some(calendar::fetch_calendar(client, year)?)
+    } else {
+        None
+    };
+
+    let days = calendar::collect(year, calendar.as_ref());
+    print!("{}", calendar::render_grid(year, &days));
+
+    if args.readme {
+        let readme = fs::read_to_string(README_PATH).context("Could not read README.md")?;
+        let table = calendar::readme_table(year, &days);
+        fs::write(README_PATH, calendar::update_readme(&readme, year, &table))
+            .context("Could not write README.md")?;
+        println!("Updated the progress table of {year} in README.md");
+    }
+    Ok(())
+}
+
+fn check_auth(client: &Client, year: Option<u16>) -> Result<()> {
+    let year = year.unwrap_or_else(|| Date::today().latest_event_year());
+    let user = auth::check_session(client, year)?;
+
+    print!(
+        "The session cookie in {} is valid, logged in as {}",
+        client.session_var(),
+        user.name
+    );
+    match user.stars {
+        Some(stars) => println!(" with {stars} stars in {year}"),
+        None => println!(),
+    }
+    Ok(())
 }
@@ -0,0 +1,272 @@
+use aoc_utils::Answer;
+
+use crate::html::{self, Token};
+
+/// The description of a puzzle. Part two is only included once part one is solved.
+pub struct Puzzle {
+    pub parts: Vec<PuzzlePart>,
+}
+
+pub struct PuzzlePart {
+    pub markdown: String,
+    /// The first code block of the part, which usually is the example input.
+    pub example: Option<String>,
+    /// The last emphasized code of the part, which usually is the answer for the example.
+    pub example_answer: Option<Answer>,
+}
+
+impl Puzzle {
+    /// Reads the `<article>` sections of a puzzle page, relative links are resolved against its URL.
+    pub fn from_html(html: &str, page_url: &str) -> Puzzle {
+        let tokens = html::tokenize(html);
+        let parts = html::articles(&tokens)
+            .into_iter()
+            .map(|article| PuzzlePart {
+                markdown: to_markdown(article, page_url),
+                example: first_code_block(article),
+                example_answer: last_emphasized_code(article).map(|code| code.parse().unwrap()),
+            })
+            .collect();
+        Puzzle { parts }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let parts: Vec<&str> = self
+            .parts
+            .iter()
+            .map(|part| part.markdown.as_str())
+            .collect();
+        let mut markdown = parts.join("\n\n");
+        markdown.push('\n');
+        markdown
+    }
+
+    /// Returns the example input and the expected answers of both parts.
+    ///
+    /// Part two usually reuses the example of part one, its answer is only returned in that case
+    /// because the generated tests use the same input for both parts.
+    pub fn examples(&self) -> (Option<&str>, [Option<&Answer>; 2]) {
+        let Some(part_one) = self.parts.first() else {
+            return (None, [None, None]);
+        };
+
+        let answer_b = self.parts.get(1).and_then(|part_two| {
+            let same_example = part_two.example.is_none() || part_two.example == part_one.example;
+            part_two.example_answer.as_ref().filter(|_| same_example)
+        });
+        (
+            part_one.example.as_deref(),
+            [part_one.example_answer.as_ref(), answer_b],
+        )
+    }
+}
+
+/// Returns the text of the first `<pre>` block.
+fn first_code_block(tokens: &[Token]) -> Option<String> {
+    let start = tokens.iter().position(|token| token.is_start("pre"))?;
+    let end = start
+        + tokens[start..]
+            .iter()
+            .position(|token| token.is_end("pre"))?;
+    Some(text(&tokens[start..end]))
+}
+
+/// Returns the text of the last `<code>` that contains emphasized text, e.g. `<code><em>11</em></code>`.
+fn last_emphasized_code(tokens: &[Token]) -> Option<String> {
+    let mut last = None;
+    let mut code_start = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_start("code") {
+            code_start = Some(i);
+        } else if token.is_end("code") {
+            let code = &tokens[code_start.take().unwrap_or(i)..i];
+            if code.iter().any(|token| token.is_start("em")) {
+                last = Some(text(code));
+            }
+        }
+    }
+
+    last
+}
+
+fn text(tokens: &[Token]) -> String {
+    tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Converts the markup used in puzzle descriptions to Markdown. Emphasized text, which contains
+/// the important parts of the puzzle, is written in bold.
+fn to_markdown(tokens: &[Token], page_url: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    // Start of the inline code that is being written and whether it is emphasized.
+    let mut code: Option<(usize, bool)> = None;
+    let mut links = Vec::new();
+
+    for token in tokens {
+        match token {
+            Token::Text(text) if in_pre => markdown.push_str(text),
+            Token::Text(text) => {
+                // Line breaks in the markup are only formatting, the text continues on the same line.
+                let text = text.replace('\n', " ");
+                if markdown.is_empty() || markdown.ends_with('\n') {
+                    markdown.push_str(text.trim_start());
+                } else {
+                    markdown.push_str(&text);
+                }
+            }
+            Token::Start { name, .. } => match name.as_str() {
+                "h2" => markdown.push_str("## "),
+                "pre" => {
+                    in_pre = true;
+                    markdown.push_str("```\n");
+                }
+                "code" if !in_pre => {
+                    code = Some((markdown.len(), false));
+                    markdown.push('`');
+                }
+                // Emphasis can't be written inside of inline code, so the whole code gets emphasized.
+                "em" if !in_pre => match &mut code {
+                    Some((start, emphasized)) if !*emphasized => {
+                        markdown.insert_str(*start, "**");
+                        *emphasized = true;
+                    }
+                    Some(_) => {}
+                    None => markdown.push_str("**"),
+                },
+                "li" => markdown.push_str("- "),
+                "a" => {
+                    markdown.push('[');
+                    links.push(token.attribute("href").unwrap_or_default().to_string());
+                }
+                _ => {}
+            },
+            Token::End(name) => match name.as_str() {
+                "h2" | "p" | "ul" => end_block(&mut markdown),
+                "pre" => {
+                    in_pre = false;
+                    if !markdown.ends_with('\n') {
+                        markdown.push('\n');
+                    }
+                    markdown.push_str("```");
+                    end_block(&mut markdown);
+                }
+                "code" if !in_pre => match code.take() {
+                    Some((_, true)) => markdown.push_str("`**"),
+                    _ => markdown.push('`'),
+                },
+                "em" if !in_pre && code.is_none() => markdown.push_str("**"),
+                "li" => markdown.push('\n'),
+                "a" => {
+                    let href = links.pop().unwrap_or_default();
+                    markdown.push_str(&format!("]({})", resolve_link(page_url, &href)));
+                }
+                _ => {}
+            },
+        }
+    }
+
+    markdown.trim_end().to_string()
+}
+
+fn resolve_link(page_url: &str, href: &str) -> String {
+    if href.contains("://") {
+        href.to_string()
+    } else if href.starts_with('/') {
+        // The path is relative to the origin of the page, e.g. `https://adventofcode.com`.
+        let path_start = page_url.find("://").and_then(|scheme_end| {
+            let host_start = scheme_end + "://".len();
+            page_url[host_start..].find('/').map(|i| host_start + i)
+        });
+        format!(
+            "{}{href}",
+            &page_url[..path_start.unwrap_or(page_url.len())]
+        )
+    } else {
+        let directory = page_url.rsplit_once('/').map_or(page_url, |(dir, _)| dir);
+        format!("{directory}/{href}")
+    }
+}
+
+/// Ends the current block with an empty line, unless there already is one.
+fn end_block(markdown: &mut String) {
+    let trimmed = markdown.trim_end_matches([' ', '\n']).len();
+    markdown.truncate(trimmed);
+    markdown.push_str("\n\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../fixtures/day01.html");
+    const FIXTURE_URL: &str = "https://adventofcode.com/2024/day/1";
+
+    #[test]
+    fn test_parts() {
+        let puzzle = Puzzle::from_html(FIXTURE, FIXTURE_URL);
+        assert_eq!(puzzle.parts.len(), 2);
+        assert!(puzzle.parts[0]
+            .markdown
+            .starts_with("## --- Day 1: Sonar Lists ---\n\n"));
+        assert!(puzzle.parts[1]
+            .markdown
+            .starts_with("## --- Part Two ---\n\n"));
+    }
+
+    #[test]
+    fn test_examples() {
+        let puzzle = Puzzle::from_html(FIXTURE, FIXTURE_URL);
+        assert_eq!(
+            puzzle.parts[0].example.as_deref(),
+            Some("3   4\n4   3\n2   5\n")
+        );
+        assert_eq!(puzzle.parts[0].example_answer, Some(Answer::Number(11)));
+        assert_eq!(puzzle.parts[1].example_answer, Some(Answer::Number(31)));
+
+        // Part two of the fixture has its own example, so its answer doesn't fit the example of part one.
+        let (example, answers) = puzzle.examples();
+        assert_eq!(example, Some("3   4\n4   3\n2   5\n"));
+        assert_eq!(answers, [Some(&Answer::Number(11)), None]);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = Puzzle::from_html(FIXTURE, FIXTURE_URL).to_markdown();
+        assert!(markdown.contains(
+            "The [sonar](https://en.wikipedia.org/wiki/Sonar) pings the sea floor and returns **two lists** of depths."
+        ));
+        assert!(markdown.contains("```\n3   4\n4   3\n2   5\n```\n"));
+        assert!(markdown.contains("- The smallest numbers `1` and `3` are `2` apart.\n"));
+        assert!(markdown.contains("the total distance is **`11`**."));
+        assert!(markdown.contains("[input](https://adventofcode.com/2024/day/1/input)"));
+        assert!(!markdown.contains("Your puzzle answer was"));
+        assert!(markdown.ends_with("**`31`**.\n"));
+    }
+
+    #[test]
+    fn test_resolve_link() {
+        assert_eq!(
+            resolve_link(FIXTURE_URL, "/2024"),
+            "https://adventofcode.com/2024"
+        );
+        assert_eq!(
+            resolve_link(FIXTURE_URL, "https://example.com"),
+            "https://example.com"
+        );
+        assert_eq!(
+            resolve_link(FIXTURE_URL, "1/input"),
+            "https://adventofcode.com/2024/day/1/input"
+        );
+        assert_eq!(
+            resolve_link("http://127.0.0.1:8080/2024/day/1", "/2024"),
+            "http://127.0.0.1:8080/2024"
+        );
+    }
+}
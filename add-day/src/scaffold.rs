@@ -0,0 +1,211 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_utils::input_file_name;
+
+use crate::error::{IoContext, Result};
+use crate::puzzle::Puzzle;
+use crate::template::{self, TemplateValues};
+
+pub const PUZZLE_FILE: &str = "PUZZLE.md";
+
+/// What happens to a file of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Create,
+    Overwrite,
+    Keep,
+}
+
+impl Action {
+    /// Existing files are only overwritten if `force` is set.
+    fn for_file(path: &Path, force: bool) -> Action {
+        match (path.exists(), force) {
+            (false, _) => Action::Create,
+            (true, true) => Action::Overwrite,
+            (true, false) => Action::Keep,
+        }
+    }
+
+    pub fn writes(self) -> bool {
+        self != Action::Keep
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Action::Create => "create",
+            Action::Overwrite => "overwrite",
+            Action::Keep => "keep",
+        })
+    }
+}
+
+/// The files of the crate of a day that are created, so that running add-day again only adds
+/// the pieces that are missing, e.g. an input that couldn't be downloaded before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub day_dir: PathBuf,
+    day: u8,
+    pub cargo_toml: Action,
+    pub main_rs: Action,
+    pub input: Action,
+    /// `None` if nothing is downloaded.
+    pub puzzle: Option<Action>,
+}
+
+impl Plan {
+    pub fn new(day_dir: PathBuf, day: u8, force: bool, fetch: bool) -> Plan {
+        let input_path = day_dir.join(input_file_name(day));
+        // An empty input is left behind when it couldn't be downloaded, so it is downloaded again.
+        let input_missing =
+            fetch && fs::metadata(&input_path).is_ok_and(|metadata| metadata.len() == 0);
+        // Without downloading, an existing input would only be replaced by an empty one.
+        let input = match Action::for_file(&input_path, force && fetch) {
+            Action::Keep if input_missing => Action::Overwrite,
+            action => action,
+        };
+
+        Plan {
+            cargo_toml: Action::for_file(&day_dir.join("Cargo.toml"), force),
+            main_rs: Action::for_file(&day_dir.join("src/main.rs"), force),
+            input,
+            puzzle: fetch.then(|| Action::for_file(&day_dir.join(PUZZLE_FILE), force)),
+            day_dir,
+            day,
+        }
+    }
+
+    /// Whether all files of the day exist already.
+    pub fn is_complete(&self) -> bool {
+        let actions = [self.cargo_toml, self.main_rs, self.input];
+        !actions.into_iter().chain(self.puzzle).any(Action::writes)
+    }
+
+    /// The puzzle description is needed for PUZZLE.md and for the examples in the main.rs.
+    pub fn needs_puzzle(&self) -> bool {
+        self.puzzle
+            .is_some_and(|puzzle| puzzle.writes() || self.main_rs.writes())
+    }
+
+    pub fn needs_input(&self) -> bool {
+        self.puzzle.is_some() && self.input.writes()
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let input_file = input_file_name(self.day);
+        let files = [
+            (Some(self.cargo_toml), "Cargo.toml"),
+            (Some(self.main_rs), "src/main.rs"),
+            (Some(self.input), input_file.as_str()),
+            (self.puzzle, PUZZLE_FILE),
+        ];
+        for (action, file) in files {
+            if let Some(action) = action {
+                writeln!(f, "{action:<9} {}/{file}", self.day_dir.display())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn create_day_dir(day_dir: &Path) -> Result<()> {
+    fs::create_dir_all(day_dir).context("Could not create day directory")
+}
+
+pub fn create_cargo_toml(year: u16, day: u8, day_dir: &Path) -> Result<()> {
+    let cargo_toml_path = day_dir.join("Cargo.toml");
+    let cargo_toml_str = template::cargo_toml(year, day)?;
+    fs::write(cargo_toml_path, cargo_toml_str).context("Could not write Cargo.toml")
+}
+
+pub fn create_src(day_dir: &Path, template: &str, values: &TemplateValues) -> Result<()> {
+    let src_dir = &day_dir.join("src");
+    fs::create_dir_all(src_dir).context("Could not create src directory")?;
+
+    let main_rs_path = src_dir.join("main.rs");
+    let main_rs_str = template::render(&template::load(template)?, values);
+    fs::write(main_rs_path, main_rs_str).context("Could not write main.rs")
+}
+
+/// Renders the main.rs again with new values, unless it was changed since it was rendered with the
+/// previous values. Returns whether the main.rs was updated.
+pub fn update_src(
+    day_dir: &Path,
+    template: &str,
+    previous: &TemplateValues,
+    values: &TemplateValues,
+) -> Result<bool> {
+    let main_rs_path = day_dir.join("src/main.rs");
+    let main_rs_str = fs::read_to_string(&main_rs_path).context("Could not read main.rs")?;
+    let template = template::load(template)?;
+    if main_rs_str != template::render(&template, previous) {
+        return Ok(false);
+    }
+
+    fs::write(main_rs_path, template::render(&template, values))
+        .context("Could not write main.rs")?;
+    Ok(true)
+}
+
+/// Writes the puzzle input of the day, or an empty input file if it wasn't fetched.
+pub fn create_input_file(day: u8, day_dir: &Path, input: Option<&str>) -> Result<()> {
+    let input_file_path = day_dir.join(input_file_name(day));
+
+    fs::write(input_file_path, input.unwrap_or_default()).context("Could not write input file")
+}
+
+/// Writes the description of the puzzle to PUZZLE.md.
+pub fn create_puzzle_file(day_dir: &Path, puzzle: &Puzzle) -> Result<()> {
+    fs::write(day_dir.join(PUZZLE_FILE), puzzle.to_markdown()).context("Could not write PUZZLE.md")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_new_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = Plan::new(dir.path().join("day05"), 5, false, true);
+        assert_eq!(plan.cargo_toml, Action::Create);
+        assert_eq!(plan.main_rs, Action::Create);
+        assert_eq!(plan.input, Action::Create);
+        assert_eq!(plan.puzzle, Some(Action::Create));
+        assert!(plan.needs_puzzle() && plan.needs_input());
+    }
+
+    #[test]
+    fn test_plan_existing_day() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        fs::write(dir.path().join("src/main.rs"), "").unwrap();
+        fs::write(dir.path().join("input_day05.txt"), "").unwrap();
+
+        let plan = Plan::new(dir.path().to_path_buf(), 5, false, false);
+        assert!(plan.is_complete());
+        let plan = Plan::new(dir.path().to_path_buf(), 5, true, false);
+        assert_eq!(plan.main_rs, Action::Overwrite);
+        assert_eq!(plan.input, Action::Keep);
+
+        // The empty input is downloaded again, together with the missing PUZZLE.md
+        let plan = Plan::new(dir.path().to_path_buf(), 5, false, true);
+        assert_eq!(plan.cargo_toml, Action::Keep);
+        assert_eq!(plan.main_rs, Action::Keep);
+        assert_eq!(plan.input, Action::Overwrite);
+        assert_eq!(plan.puzzle, Some(Action::Create));
+
+        fs::write(dir.path().join("input_day05.txt"), "1 2 3").unwrap();
+        fs::write(dir.path().join(PUZZLE_FILE), "").unwrap();
+        assert!(Plan::new(dir.path().to_path_buf(), 5, false, true).is_complete());
+
+        let plan = Plan::new(dir.path().to_path_buf(), 5, true, true);
+        assert_eq!(plan.main_rs, Action::Overwrite);
+        assert_eq!(plan.input, Action::Overwrite);
+    }
+}
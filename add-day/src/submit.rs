@@ -0,0 +1,249 @@
+use std::fmt::{self, Display};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_utils::{answer, day_package_name, Answer};
+use clap::ValueEnum;
+
+use crate::error::{Error, IoContext, Result};
+use crate::html;
+
+/// A part of a puzzle, named like in the output of the days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    #[value(alias = "1", alias = "a")]
+    A,
+    #[value(alias = "2", alias = "b")]
+    B,
+}
+
+impl Part {
+    /// The level of the part in the answer form of Advent of Code.
+    pub fn level(self) -> u8 {
+        match self {
+            Part::A => 1,
+            Part::B => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::A => write!(f, "A"),
+            Part::B => write!(f, "B"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Part::A),
+            "B" => Ok(Part::B),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How Advent of Code responded to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Answers can only be submitted once a minute, or less often after wrong answers.
+    RateLimited(Option<Duration>),
+    /// The part was already solved, or part one has to be solved first.
+    AlreadySolved,
+    /// A response that couldn't be classified, with the text of its message.
+    Unknown(String),
+}
+
+impl Outcome {
+    /// Classifies the page that is returned after submitting an answer by the text of its article.
+    pub fn from_response(html: &str) -> Outcome {
+        let tokens = html::tokenize(html);
+        let message: String = html::articles(&tokens)
+            .into_iter()
+            .flatten()
+            .filter_map(|token| match token {
+                html::Token::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        let message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if message.contains("That's the right answer") {
+            Outcome::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if message.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Incorrect
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Outcome::RateLimited(parse_wait_time(&message))
+        } else if message.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown(message)
+        }
+    }
+
+    /// Whether the answer was rejected, so that submitting it again would be wrong as well.
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+
+    /// Identifier of the outcome in the submission history.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Incorrect => "incorrect",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::RateLimited(_) => "rate-limited",
+            Outcome::AlreadySolved => "already-solved",
+            Outcome::Unknown(_) => "unknown",
+        }
+    }
+
+    /// Reads an identifier written by [`Outcome::key`], details like the wait time are not stored.
+    pub fn from_key(key: &str) -> Option<Outcome> {
+        match key {
+            "correct" => Some(Outcome::Correct),
+            "incorrect" => Some(Outcome::Incorrect),
+            "too-high" => Some(Outcome::TooHigh),
+            "too-low" => Some(Outcome::TooLow),
+            "rate-limited" => Some(Outcome::RateLimited(None)),
+            "already-solved" => Some(Outcome::AlreadySolved),
+            "unknown" => Some(Outcome::Unknown(String::new())),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::Incorrect => write!(f, "That's not the right answer."),
+            Outcome::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            Outcome::TooLow => write!(f, "That's not the right answer, it is too low."),
+            Outcome::RateLimited(Some(wait)) => write!(
+                f,
+                "An answer was submitted too recently, try again in {}s.",
+                wait.as_secs()
+            ),
+            Outcome::RateLimited(None) => write!(f, "An answer was submitted too recently."),
+            Outcome::AlreadySolved => write!(
+                f,
+                "This part was already solved, or part one has to be solved first."
+            ),
+            Outcome::Unknown(message) => write!(f, "Unexpected response: {message}"),
+        }
+    }
+}
+
+/// Runs the solution of the day in release mode and takes the answer of the part from its output.
+pub fn solution_answer(year: u16, day: u8, part: Part) -> Result<Answer> {
+    let package = day_package_name(year, day);
+    let solution_error = |message: String| Error::Solution {
+        package: package.clone(),
+        message,
+    };
+
+    println!("Running {package} to get the answer of part {part}...");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["run", "--release", "--quiet", "-p", &package])
+        .output()
+        .context(format!("Could not run {package}"))?;
+    if !output.status.success() {
+        return Err(solution_error(format!("failed with {}", output.status)));
+    }
+
+    let (a, b) = answer::parse_output(&String::from_utf8_lossy(&output.stdout));
+    let answer = match part {
+        Part::A => a,
+        Part::B => b,
+    };
+    match answer {
+        Some(answer) if answer.is_multiline() => Err(solution_error(format!(
+            "the answer of part {part} spans multiple lines, pass the letters it shows as answer"
+        ))),
+        Some(answer) => Ok(answer),
+        None => Err(solution_error(format!("printed no answer for part {part}"))),
+    }
+}
+
+/// Reads the wait time from messages like "You have 1m 23s left to wait."
+fn parse_wait_time(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("You have ")?;
+    let (time, _) = rest.split_once(" left to wait")?;
+
+    time.split_whitespace()
+        .map(|part| {
+            let unit_start = part.find(|c: char| !c.is_ascii_digit())?;
+            let value: u64 = part[..unit_start].parse().ok()?;
+            let seconds = match &part[unit_start..] {
+                "h" => 60 * 60,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            Some(Duration::from_secs(value * seconds))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn test_classify_responses() {
+        let cases = [
+            ("That's the right answer!  You are <em>one gold star</em> closer.", Outcome::Correct),
+            ("That's not the right answer.  If you're stuck, try the example.", Outcome::Incorrect),
+            ("That's not the right answer; your answer is too high.", Outcome::TooHigh),
+            ("That's not the right answer; your answer is too low.", Outcome::TooLow),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Outcome::AlreadySolved,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.",
+                Outcome::RateLimited(Some(Duration::from_secs(83))),
+            ),
+            ("Something new", Outcome::Unknown("Something new".to_string())),
+        ];
+
+        for (message, outcome) in cases {
+            assert_eq!(Outcome::from_response(&response(message)), outcome);
+        }
+    }
+
+    #[test]
+    fn test_parse_wait_time() {
+        assert_eq!(
+            parse_wait_time("You have 37s left to wait."),
+            Some(Duration::from_secs(37))
+        );
+        assert_eq!(parse_wait_time("Please wait."), None);
+    }
+}
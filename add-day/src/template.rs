@@ -0,0 +1,287 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_utils::{day_package_name, Answer};
+
+use crate::error::{Error, IoContext, Result};
+
+/// The day00 crate, which new days are copied from unless another template is selected.
+pub const DAY00_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../day00");
+/// Directory containing alternative templates for the main.rs of new days, one `<name>.rs` file per template.
+pub const TEMPLATES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/templates");
+
+pub const DEFAULT_TEMPLATE: &str = "day00";
+/// Name of the template that reads the input line by line. It is the same as day00, so that both
+/// can't drift apart.
+const LINES_TEMPLATE: &str = "lines";
+
+/// Values that replace the `{{year}}`, `{{day}}`, `{{title}}`, `{{example}}`, `{{answer_a}}` and
+/// `{{answer_b}}` placeholders of a template.
+pub struct TemplateValues {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    /// Example input from the puzzle description, an empty string is used if there is none.
+    pub example: Option<String>,
+    /// Expected answers of both parts for the example, `0` is used for unknown answers.
+    pub answers: [Option<Answer>; 2],
+}
+
+/// Reads the template with the given name. The day00 template is created from the main.rs of day00
+/// by replacing its title and constants with placeholders, `lines` is another name for it.
+pub fn load(name: &str) -> Result<String> {
+    if name == DEFAULT_TEMPLATE || name == LINES_TEMPLATE {
+        let path = Path::new(DAY00_DIR).join("src/main.rs");
+        let main_rs = read(&path)?;
+        return day00_template(&main_rs).ok_or(Error::InvalidTemplate(path));
+    }
+
+    let path = template_path(name);
+    if !path.exists() {
+        return Err(Error::UnknownTemplate {
+            name: name.to_string(),
+            available: available()?,
+        });
+    }
+
+    read(&path)
+}
+
+/// Names of all templates, starting with the default one and its alias.
+pub fn available() -> Result<Vec<String>> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .context("Could not read templates directory")?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "rs").then(|| path.file_stem()?.to_str().map(String::from))?
+        })
+        .collect();
+    names.sort();
+    names.insert(0, LINES_TEMPLATE.to_string());
+    names.insert(0, DEFAULT_TEMPLATE.to_string());
+    Ok(names)
+}
+
+pub fn render(template: &str, values: &TemplateValues) -> String {
+    template
+        .replace("{{year}}", &values.year.to_string())
+        .replace("{{day}}", &values.day.to_string())
+        .replace("{{title}}", &values.title)
+        .replace(
+            "{{example}}",
+            &string_literal(values.example.as_deref().unwrap_or_default()),
+        )
+        .replace(
+            "{{answer_a}}",
+            &answer_expression(values.answers[0].as_ref()),
+        )
+        .replace(
+            "{{answer_b}}",
+            &answer_expression(values.answers[1].as_ref()),
+        )
+}
+
+/// Copies the Cargo.toml of day00, renamed to the package of the day. The path to aoc-utils
+/// gets an additional `../`, because days are located in the directory of their year.
+pub fn cargo_toml(year: u16, day: u8) -> Result<String> {
+    let path = Path::new(DAY00_DIR).join("Cargo.toml");
+    let cargo_toml = read(&path)?;
+
+    let name = r#"name = "day00""#;
+    let dependency = r#"aoc-utils = { path = "../aoc-utils" }"#;
+    if !cargo_toml.contains(name) || !cargo_toml.contains(dependency) {
+        return Err(Error::InvalidTemplate(path));
+    }
+
+    Ok(cargo_toml
+        .replace(
+            name,
+            &format!(r#"name = "{}""#, day_package_name(year, day)),
+        )
+        .replace(dependency, r#"aoc-utils = { path = "../../aoc-utils" }"#))
+}
+
+/// Replaces the title comment, the YEAR and DAY constants and the example test of day00 with placeholders.
+/// Returns `None` if one of them is missing, so that changes to day00 can't silently break new days.
+fn day00_template(main_rs: &str) -> Option<String> {
+    let mut found = [false; 6];
+    let mut template = String::with_capacity(main_rs.len());
+
+    for line in main_rs.split_inclusive('\n') {
+        let code = line.trim_start();
+        let replacement = if code.starts_with("//! ") && !found[0] {
+            (0, "//! {{title}}")
+        } else if code.starts_with("const YEAR: u16 = ") {
+            (1, "const YEAR: u16 = {{year}};")
+        } else if code.starts_with("const DAY: u8 = ") {
+            (2, "const DAY: u8 = {{day}};")
+        } else if code.starts_with("const TEST_INPUT: &str = ") {
+            (3, "const TEST_INPUT: &str = {{example}};")
+        } else if code.starts_with("test_solve_a: ") {
+            (4, "test_solve_a: TEST_INPUT, solve_a => {{answer_a}};")
+        } else if code.starts_with("test_solve_b: ") {
+            (5, "test_solve_b: TEST_INPUT, solve_b => {{answer_b}};")
+        } else {
+            template.push_str(line);
+            continue;
+        };
+
+        found[replacement.0] = true;
+        template.push_str(&line[..line.len() - code.len()]);
+        template.push_str(replacement.1);
+        template.push('\n');
+    }
+
+    found.iter().all(|f| *f).then_some(template)
+}
+
+/// Writes the text as string literal, keeping its line breaks like the examples in the existing days.
+fn string_literal(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn answer_expression(answer: Option<&Answer>) -> String {
+    match answer {
+        Some(Answer::Number(n)) => n.to_string(),
+        Some(Answer::Text(text)) => string_literal(text),
+        None => "0".to_string(),
+    }
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).context(format!("Could not read template {}", path.display()))
+}
+
+fn template_path(name: &str) -> PathBuf {
+    Path::new(TEMPLATES_DIR).join(format!("{name}.rs"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn values() -> TemplateValues {
+        TemplateValues {
+            year: 2024,
+            day: 8,
+            title: "Day 8: Resonant Collinearity".to_string(),
+            example: Some("..#\n\"\\.\n".to_string()),
+            answers: [Some(Answer::Number(14)), Some(Answer::from("a,b"))],
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(
+            "//! {{title}}\nconst YEAR: u16 = {{year}};\nconst DAY: u8 = {{day}};",
+            &values(),
+        );
+        assert_eq!(
+            rendered,
+            "//! Day 8: Resonant Collinearity\nconst YEAR: u16 = 2024;\nconst DAY: u8 = 8;"
+        );
+    }
+
+    #[test]
+    fn test_render_examples() {
+        let rendered = render(
+            "const TEST_INPUT: &str = {{example}};\nsolve_a => {{answer_a}};\nsolve_b => {{answer_b}};",
+            &values(),
+        );
+        assert_eq!(
+            rendered,
+            "const TEST_INPUT: &str = \"..#\n\\\"\\\\.\n\";\nsolve_a => 14;\nsolve_b => \"a,b\";"
+        );
+    }
+
+    #[test]
+    fn test_templates_have_no_unknown_placeholders() {
+        for name in available().unwrap() {
+            let rendered = render(&load(&name).unwrap(), &values());
+            assert!(
+                !rendered.contains("{{"),
+                "template {name} has unknown placeholders"
+            );
+        }
+    }
+
+    #[test]
+    fn test_lines_template_is_day00() {
+        assert_eq!(available().unwrap()[..2], ["day00", "lines"]);
+        assert_eq!(
+            load(LINES_TEMPLATE).unwrap(),
+            load(DEFAULT_TEMPLATE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unknown_template() {
+        assert!(matches!(
+            load("does-not-exist"),
+            Err(Error::UnknownTemplate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_day00_template_matches_day00() {
+        let day00 = TemplateValues {
+            year: 2024,
+            day: 0,
+            title: "Day 0".to_string(),
+            example: None,
+            answers: [None, None],
+        };
+        let main_rs = fs::read_to_string(Path::new(DAY00_DIR).join("src/main.rs")).unwrap();
+        assert_eq!(render(&load(DEFAULT_TEMPLATE).unwrap(), &day00), main_rs);
+
+        let cargo_toml = cargo_toml(2024, 8).unwrap();
+        assert!(cargo_toml.contains(r#"name = "y2024-day08""#));
+        assert!(cargo_toml.contains(r#"path = "../../aoc-utils""#));
+    }
+
+    #[test]
+    fn test_generated_days_compile() {
+        // The day is generated in the layout of the workspace, with aoc-utils copied next to it.
+        let workspace = tempfile::tempdir().unwrap();
+        let aoc_utils = Path::new(env!("CARGO_MANIFEST_DIR")).join("../aoc-utils");
+        let copied_aoc_utils = workspace.path().join("aoc-utils");
+        fs::create_dir_all(copied_aoc_utils.join("src")).unwrap();
+        fs::copy(
+            aoc_utils.join("Cargo.toml"),
+            copied_aoc_utils.join("Cargo.toml"),
+        )
+        .unwrap();
+        for entry in fs::read_dir(aoc_utils.join("src")).unwrap() {
+            let path = entry.unwrap().path();
+            fs::copy(
+                &path,
+                copied_aoc_utils.join("src").join(path.file_name().unwrap()),
+            )
+            .unwrap();
+        }
+
+        // Every template is generated as its own day, lines is skipped as it is the same as day00.
+        let templates = available().unwrap();
+        for (day, name) in (8..).zip(templates.iter().filter(|name| *name != LINES_TEMPLATE)) {
+            let day_dir = workspace.path().join(format!("y2024/day{day:02}"));
+            fs::create_dir_all(day_dir.join("src")).unwrap();
+            fs::write(day_dir.join("Cargo.toml"), cargo_toml(2024, day).unwrap()).unwrap();
+            let values = TemplateValues { day, ..values() };
+            let main_rs = render(&load(name).unwrap(), &values);
+            fs::write(day_dir.join("src/main.rs"), main_rs).unwrap();
+
+            // Reuse the dependencies that are already downloaded for the workspace.
+            let status = Command::new(env!("CARGO"))
+                .args(["check", "--offline", "--quiet", "--all-targets"])
+                .current_dir(&day_dir)
+                .env("CARGO_TARGET_DIR", workspace.path().join("target"))
+                .status()
+                .unwrap();
+            assert!(
+                status.success(),
+                "the day generated from {name} does not compile"
+            );
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::date;
+use crate::error::{Error, FetchError, Result};
+
+/// How often a request is tried before giving up, when the server is briefly unavailable.
+const ATTEMPTS: u32 = 8;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Shows a countdown until the puzzle of the day unlocks.
+pub fn wait_for_unlock(year: u16, day: u8) {
+    let unlock = date::unlock_time(year, day);
+    let remaining = unlock - date::now();
+    if remaining <= 0 {
+        return;
+    }
+
+    let interactive = io::stdout().is_terminal();
+    if !interactive {
+        println!("Day {day} unlocks in {}", date::format_duration(remaining));
+    }
+
+    loop {
+        let remaining = unlock - date::now();
+        if remaining <= 0 {
+            break;
+        }
+        if interactive {
+            print!(
+                "\r\x1b[2KDay {day} unlocks in {}",
+                date::format_duration(remaining)
+            );
+            io::stdout().flush().ok();
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    if interactive {
+        println!("\r\x1b[2KDay {day} is unlocked!");
+    }
+}
+
+/// Runs the request until it succeeds, retrying with exponential backoff while the error is
+/// temporary, e.g. because the servers are overloaded right after the unlock.
+pub fn with_retries<T>(mut request: impl FnMut() -> Result<T>) -> Result<T> {
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 1.. {
+        match request() {
+            Err(Error::Fetch { error, url }) if attempt < ATTEMPTS && is_temporary(&error) => {
+                println!(
+                    "Could not fetch {url}: {error}. Retrying in {}s ({attempt}/{ATTEMPTS})...",
+                    backoff.as_secs()
+                );
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            result => return result,
+        }
+    }
+
+    unreachable!("the last attempt returns its result")
+}
+
+/// Errors that can go away by trying again a bit later. A puzzle that is not unlocked yet is one
+/// of them, because the local clock can be a few seconds ahead of the one of the server.
+fn is_temporary(error: &FetchError) -> bool {
+    matches!(
+        error,
+        FetchError::NotUnlocked
+            | FetchError::ServerError(_)
+            | FetchError::TooManyRequests
+            | FetchError::Network(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retries_only_temporary_errors() {
+        let mut attempts = 0;
+        let result: Result<()> = with_retries(|| {
+            attempts += 1;
+            Err(Error::Fetch {
+                url: String::new(),
+                error: FetchError::InvalidSession,
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_too_many_requests_is_temporary() {
+        assert!(is_temporary(&FetchError::TooManyRequests));
+        assert!(is_temporary(&FetchError::NotUnlocked));
+        assert!(!is_temporary(&FetchError::UnexpectedStatus(418)));
+    }
+}
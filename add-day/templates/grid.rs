@@ -0,0 +1,42 @@
+//! {{title}}
+use aoc_utils::PuzzleInput;
+const YEAR: u16 = {{year}};
+const DAY: u8 = {{day}};
+
+fn main() {
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
+}
+
+fn parse_grid(input: &PuzzleInput) -> Vec<Vec<char>> {
+    input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.chars().collect())
+        .collect()
+}
+
+fn solve_a(input: &PuzzleInput) -> usize {
+    let grid = parse_grid(input);
+    grid.iter().flatten().count()
+}
+
+fn solve_b(input: &PuzzleInput) -> usize {
+    let grid = parse_grid(input);
+    grid.iter().flatten().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const TIME_BUDGET: Duration = Duration::from_millis(100);
+
+    const TEST_INPUT: &str = {{example}};
+
+    aoc_utils::day_tests! {
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a, solve_b;
+        test_solve_a: TEST_INPUT, solve_a => {{answer_a}};
+        test_solve_b: TEST_INPUT, solve_b => {{answer_b}};
+    }
+}
@@ -0,0 +1,50 @@
+//! {{title}}
+use aoc_utils::PuzzleInput;
+const YEAR: u16 = {{year}};
+const DAY: u8 = {{day}};
+
+fn main() {
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
+}
+
+/// Splits the input into the lines of the two sections separated by an empty line.
+fn parse_sections(input: &PuzzleInput) -> (Vec<String>, Vec<String>) {
+    let mut sections = input.raw_input.split("\n\n");
+    let mut section_lines = || -> Vec<String> {
+        sections
+            .next()
+            .unwrap_or_default()
+            .lines()
+            .map(|l| l.to_string())
+            .collect()
+    };
+    let first = section_lines();
+    let second = section_lines();
+    (first, second)
+}
+
+fn solve_a(input: &PuzzleInput) -> usize {
+    let (first, _second) = parse_sections(input);
+    first.len()
+}
+
+fn solve_b(input: &PuzzleInput) -> usize {
+    let (_first, second) = parse_sections(input);
+    second.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const TIME_BUDGET: Duration = Duration::from_millis(100);
+
+    const TEST_INPUT: &str = {{example}};
+
+    aoc_utils::day_tests! {
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a, solve_b;
+        test_solve_a: TEST_INPUT, solve_a => {{answer_a}};
+        test_solve_b: TEST_INPUT, solve_b => {{answer_b}};
+    }
+}
@@ -0,0 +1,255 @@
+//! A mock of Advent of Code for testing the requests of add-day without the real site.
+//! It serves the fixtures of add-day as the pages of the user `Alice`, checks the session cookie, responds with 404 to days that
+//! aren't unlocked and rate limits answers after a wrong one, like Advent of Code does.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The session cookie that the mock server accepts.
+pub const SESSION: &str = "mock-session";
+pub const INPUT: &str = "3   4\n4   3\n2   5\n";
+
+const PUZZLE: &str = include_str!("../../fixtures/day01.html");
+const CALENDAR: &str = include_str!("../../fixtures/calendar.html");
+const LEADERBOARD: &str = include_str!("../../fixtures/leaderboard.json");
+
+pub struct Config {
+    /// Days after this one respond with 404.
+    pub unlocked_days: u8,
+    /// The correct answers of both parts, the same for every day.
+    pub answers: [&'static str; 2],
+    /// How long answers are rate limited after a wrong answer.
+    pub wrong_answer_delay: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            unlocked_days: 1,
+            answers: ["42", "24"],
+            wrong_answer_delay: Duration::ZERO,
+        }
+    }
+}
+
+/// A request received by the mock server.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub user_agent: Option<String>,
+    pub session: Option<String>,
+    pub body: String,
+}
+
+struct Response {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: &str) -> Response {
+        Response::with_status(200, body)
+    }
+
+    fn with_status(status: u16, body: &str) -> Response {
+        Response {
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+}
+
+struct State {
+    config: Config,
+    requests: Vec<Request>,
+    /// Answers are rate limited until this time.
+    answer_blocked_until: Option<Instant>,
+}
+
+/// The mock server, which runs on a free port until the test ends.
+pub struct MockServer {
+    pub url: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockServer {
+    pub fn start(config: Config) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State {
+            config,
+            requests: Vec::new(),
+            answer_blocked_until: None,
+        }));
+
+        let server_state = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                handle_connection(stream.unwrap(), &server_state);
+            }
+        });
+        MockServer { url, state }
+    }
+
+    /// All requests received so far, in order.
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, state: &Mutex<State>) {
+    let request = read_request(&mut stream);
+    let response = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(request.clone());
+        respond(&mut state, &request)
+    };
+
+    let mut head = format!("HTTP/1.1 {} Mock\r\n", response.status);
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    ));
+    stream.write_all(head.as_bytes()).unwrap();
+    stream.write_all(response.body.as_bytes()).unwrap();
+}
+
+fn read_request(stream: &mut TcpStream) -> Request {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut user_agent = None;
+    let mut session = None;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).unwrap();
+        let Some((name, value)) = header.trim_end().split_once(": ") else {
+            break;
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "user-agent" => user_agent = Some(value.to_string()),
+            "cookie" => session = value.strip_prefix("session=").map(String::from),
+            "content-length" => content_length = value.parse().unwrap(),
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    Request {
+        method,
+        path,
+        user_agent,
+        session,
+        body: String::from_utf8(body).unwrap(),
+    }
+}
+
+fn respond(state: &mut State, request: &Request) -> Response {
+    let segments: Vec<&str> = request.path.trim_start_matches('/').split('/').collect();
+    let (year, rest) = match segments.split_first() {
+        Some((year, rest)) if year.parse::<u16>().is_ok() => (*year, rest),
+        _ => return Response::with_status(404, "Not found"),
+    };
+
+    // Pages that are specific to the user need a valid session cookie.
+    let needs_session = matches!(rest, ["day", _, "input" | "answer"] | ["leaderboard", ..]);
+    if needs_session {
+        match &request.session {
+            None => {
+                return Response::with_status(
+                    400,
+                    "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+                )
+            }
+            Some(session) if session != SESSION => {
+                let mut response = Response::with_status(302, "");
+                response
+                    .headers
+                    .push(("Location", format!("/{year}/auth/login")));
+                return response;
+            }
+            Some(_) => {}
+        }
+    }
+
+    match rest {
+        [] => Response::ok(&calendar(request.session.as_deref() == Some(SESSION))),
+        ["day", day, page @ ..] => {
+            let day: u8 = day.parse().unwrap_or(u8::MAX);
+            if day == 0 || day > state.config.unlocked_days {
+                return Response::with_status(404, "Not found");
+            }
+            match (request.method.as_str(), page) {
+                ("GET", []) => Response::ok(PUZZLE),
+                ("GET", ["input"]) => Response::ok(INPUT),
+                ("POST", ["answer"]) => Response::ok(&answer_page(state, &request.body)),
+                _ => Response::with_status(404, "Not found"),
+            }
+        }
+        ["leaderboard", "private", "view", _] => Response::ok(LEADERBOARD),
+        _ => Response::with_status(404, "Not found"),
+    }
+}
+
+/// The calendar of the event shows the logged in user in its header, invalid session cookies are
+/// treated as logged out.
+fn calendar(logged_in: bool) -> String {
+    if logged_in {
+        return CALENDAR.to_string();
+    }
+    CALENDAR
+        .lines()
+        .filter(|line| !line.contains(r#"<div class="user">"#))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Checks the submitted form and responds with the messages of Advent of Code.
+fn answer_page(state: &mut State, form: &str) -> String {
+    let field = |name: &str| {
+        form.split('&')
+            .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+            .unwrap_or_default()
+    };
+    let level: usize = field("level").parse().unwrap_or(1);
+    let answer = field("answer");
+    let correct = state.config.answers[level.clamp(1, 2) - 1];
+
+    let now = Instant::now();
+    let message = if let Some(wait) = state
+        .answer_blocked_until
+        .and_then(|until| until.checked_duration_since(now))
+    {
+        format!(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {}s left to wait.",
+            wait.as_secs_f64().ceil()
+        )
+    } else if answer == correct {
+        "That's the right answer!  You are one gold star closer to finding the chief historian."
+            .to_string()
+    } else {
+        state.answer_blocked_until = Some(now + state.config.wrong_answer_delay);
+        let hint = match (answer.parse::<i64>(), correct.parse::<i64>()) {
+            (Ok(answer), Ok(correct)) if answer < correct => "  your answer is too low.",
+            (Ok(answer), Ok(correct)) if answer > correct => "  your answer is too high.",
+            _ => "",
+        };
+        format!("That's not the right answer;{hint}  Please wait one minute before trying again.")
+    };
+    format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+}
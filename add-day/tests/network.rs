@@ -0,0 +1,275 @@
+//! Runs add-day against the mock server, to test downloading and submitting without Advent of Code.
+
+mod mock_server;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::Duration;
+
+use mock_server::{Config, MockServer, INPUT, SESSION};
+
+/// Command running add-day in the directory, which sends all requests to the server.
+fn command(server: &MockServer, dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_add-day"));
+    command
+        .args(args)
+        .current_dir(dir)
+        .env("AOC_URL", &server.url)
+        .env("AOC_YEAR", "2024")
+        .env_remove("AOC_SESSION")
+        .env_remove("AOC_PROFILE")
+        .env_remove("AOC_USER_AGENT")
+        .env_remove("AOC_LEADERBOARD_ID");
+    command
+}
+
+/// Runs add-day in the directory with the given session cookie.
+fn add_day(server: &MockServer, dir: &Path, session: &str, args: &[&str]) -> Output {
+    command(server, dir, args)
+        .env("AOC_SESSION", session)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn test_new_day() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = add_day(&server, dir.path(), SESSION, &["new", "1"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let day_dir = dir.path().join("y2024/day01");
+    assert_eq!(
+        fs::read_to_string(day_dir.join("input_day01.txt")).unwrap(),
+        INPUT
+    );
+    let puzzle = fs::read_to_string(day_dir.join("PUZZLE.md")).unwrap();
+    assert!(puzzle.contains("## --- Day 1: Sonar Lists ---"));
+    assert!(puzzle.contains(&format!("]({}/2024/day/1/input)", server.url)));
+    let main_rs = fs::read_to_string(day_dir.join("src/main.rs")).unwrap();
+    assert!(main_rs.contains("test_solve_a: TEST_INPUT, solve_a => 11;"));
+
+    let requests = server.requests();
+    let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(paths, ["/2024/day/1", "/2024/day/1/input"]);
+    for request in &requests {
+        assert!(request.user_agent.as_ref().unwrap().starts_with("add-day/"));
+        assert_eq!(request.session.as_deref(), Some(SESSION));
+    }
+
+    // Everything is cached, so running it again with --force doesn't send any requests.
+    let output = add_day(&server, dir.path(), SESSION, &["new", "1", "--force"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_new_day_not_unlocked() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = add_day(&server, dir.path(), SESSION, &["new", "2"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("the puzzle is not unlocked yet"));
+    assert!(!dir.path().join("y2024/day02").exists());
+}
+
+#[test]
+fn test_invalid_session() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = add_day(&server, dir.path(), "expired", &["new", "1"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("the session cookie is invalid or expired"));
+    assert!(!dir.path().join("y2024/day01").exists());
+}
+
+#[test]
+fn test_submit() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+    add_day(&server, dir.path(), SESSION, &["new", "1", "--no-fetch"]);
+
+    let output = add_day(&server, dir.path(), SESSION, &["submit", "1", "a", "40"]);
+    assert!(stdout(&output).contains("it is too low"));
+    let output = add_day(&server, dir.path(), SESSION, &["submit", "1", "a", "42"]);
+    assert!(stdout(&output).contains("That's the right answer!"));
+
+    let output = add_day(&server, dir.path(), SESSION, &["progress"]);
+    // The accepted answer counts as star, the input wasn't downloaded because of --no-fetch.
+    assert!(stdout(&output).contains(" 1 *  c-v"), "{}", stdout(&output));
+}
+
+#[test]
+fn test_submit_rate_limited() {
+    let server = MockServer::start(Config {
+        wrong_answer_delay: Duration::from_secs(60),
+        ..Config::default()
+    });
+    let dir = tempfile::tempdir().unwrap();
+    add_day(&server, dir.path(), SESSION, &["new", "1", "--no-fetch"]);
+
+    let output = add_day(&server, dir.path(), SESSION, &["submit", "1", "b", "30"]);
+    assert!(stdout(&output).contains("it is too high"));
+    let output = add_day(&server, dir.path(), SESSION, &["submit", "1", "b", "20"]);
+    assert!(stdout(&output).contains("try again in 60s"));
+
+    // Answers that are ruled out by the history are not submitted at all.
+    let output = add_day(&server, dir.path(), SESSION, &["submit", "1", "b", "31"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("not submitting the answer"));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_leaderboard() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = add_day(
+        &server,
+        dir.path(),
+        SESSION,
+        &["leaderboard", "--id", "123"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Private leaderboard 123 of 2024"));
+    assert!(stdout(&output).contains("Alice"));
+    assert_eq!(
+        server.requests()[0].path,
+        "/2024/leaderboard/private/view/123.json"
+    );
+}
+
+#[test]
+fn test_progress_online() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = add_day(&server, dir.path(), SESSION, &["progress", "--online"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Advent of Code 2024: 3 stars"));
+    assert!(stdout(&output).contains(" 1 ** ---    2 *  ---"));
+}
+
+#[test]
+fn test_auth_check() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = add_day(&server, dir.path(), SESSION, &["auth", "check"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("logged in as Alice with 3 stars in 2024"));
+
+    let output = add_day(&server, dir.path(), "expired", &["auth", "check"]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("the session cookie is invalid or expired"));
+}
+
+#[test]
+fn test_profile() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+
+    // The default session cookie is not used for profiles.
+    let output = add_day(
+        &server,
+        dir.path(),
+        SESSION,
+        &["--profile", "work", "auth", "check"],
+    );
+    assert!(stderr(&output).contains("no AOC_SESSION_WORK environment variable found"));
+
+    let output = command(&server, dir.path(), &["new", "1", "--profile", "work"])
+        .env("AOC_SESSION_WORK", SESSION)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    let day_dir = dir.path().join("y2024/day01");
+    assert_eq!(
+        fs::read_to_string(day_dir.join("input_day01.work.txt")).unwrap(),
+        INPUT
+    );
+    assert!(!day_dir.join("input_day01.txt").exists());
+}
+
+#[test]
+fn test_input_not_encrypted() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+    let status = Command::new("git")
+        .args(["init", "--quiet"])
+        .current_dir(dir.path())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let output = add_day(&server, dir.path(), SESSION, &["new", "1"]);
+    assert!(stderr(&output).contains("no git-crypt rule in .gitattributes matches it"));
+    assert!(!dir.path().join("y2024/day01").exists());
+    assert!(server.requests().is_empty());
+
+    fs::write(
+        dir.path().join(".gitattributes"),
+        "input_day*.txt filter=git-crypt diff=git-crypt\n",
+    )
+    .unwrap();
+    let output = add_day(&server, dir.path(), SESSION, &["new", "1"]);
+    assert!(stderr(&output).contains("the repository is locked"));
+
+    let args = ["new", "1", "--allow-unencrypted"];
+    let output = add_day(&server, dir.path(), SESSION, &args);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Warning: y2024/day01/input_day01.txt will not be encrypted"));
+    assert!(stdout(&output).contains("Warning: y2024/day01/PUZZLE.md will not be encrypted"));
+
+    // git-crypt keeps the key in the git directory while the repository is unlocked.
+    fs::create_dir_all(dir.path().join(".git/git-crypt/keys")).unwrap();
+    let output = add_day(&server, dir.path(), SESSION, &["fetch", "1", "--force"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!stdout(&output).contains("Warning"));
+
+    // The puzzle description and the submissions have no rule, so they are not written either.
+    let requests = server.requests().len();
+    let output = add_day(&server, dir.path(), SESSION, &["refresh", "1"]);
+    assert!(stderr(&output).contains("not writing y2024/day01/PUZZLE.md"));
+    let output = add_day(&server, dir.path(), SESSION, &["submit", "1", "a", "42"]);
+    assert!(stderr(&output).contains("not writing y2024/day01/submissions.tsv"));
+    assert_eq!(server.requests().len(), requests);
+}
+
+#[test]
+fn test_fetch_force() {
+    let server = MockServer::start(Config::default());
+    let dir = tempfile::tempdir().unwrap();
+    add_day(&server, dir.path(), SESSION, &["new", "1"]);
+    let day_dir = dir.path().join("y2024/day01");
+    fs::write(day_dir.join("input_day01.txt"), "broken").unwrap();
+
+    let output = add_day(&server, dir.path(), SESSION, &["fetch", "1"]);
+    assert!(stdout(&output).contains("present"));
+    let requests = server.requests().len();
+
+    // The cached input is not used, it is downloaded again.
+    let output = add_day(&server, dir.path(), SESSION, &["fetch", "1", "--force"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("downloaded (3 lines)"));
+    let requests = &server.requests()[requests..];
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/2024/day/1/input");
+    assert_eq!(
+        fs::read_to_string(day_dir.join("input_day01.txt")).unwrap(),
+        INPUT
+    );
+}
@@ -0,0 +1,161 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator that counts allocations and tracks the live memory on top of the system allocator.
+///
+/// It is installed for all binaries using aoc-utils when the `alloc-stats` feature is enabled,
+/// e.g. by running `cargo run -p runner -- --alloc`.
+pub struct CountingAllocator;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+impl CountingAllocator {
+    fn record_allocation(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_deallocation(size: usize) {
+        LIVE_BYTES.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_deallocation(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_deallocation(layout.size());
+            Self::record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations done while running a part of a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes_allocated: usize,
+    /// Highest amount of memory that was allocated at the same time, including the memory that was
+    /// already allocated before.
+    pub peak_live_bytes: usize,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes_allocated),
+            format_bytes(self.peak_live_bytes)
+        )
+    }
+}
+
+/// Whether the counting allocator is installed, otherwise all stats stay at zero.
+pub fn is_enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Runs the function and returns the allocations it has done.
+/// The counters are global, so this is only accurate if nothing else allocates at the same time.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes_allocated = BYTES_ALLOCATED.load(Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(LIVE_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed) - bytes_allocated,
+        peak_live_bytes: PEAK_LIVE_BYTES.load(Ordering::Relaxed),
+    };
+    (result, stats)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(12), "12 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn test_display() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes_allocated: 2048,
+            peak_live_bytes: 1024,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 allocations, 2.0 KiB allocated, 1.0 KiB peak"
+        );
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn test_measure() {
+        // Other tests may allocate at the same time, so the stats are only checked to be at least
+        // as high as the allocation of the vector.
+        let (len, stats) = super::measure(|| std::hint::black_box(vec![0u8; 4096]).len());
+        assert_eq!(len, 4096);
+        assert!(stats.allocations >= 1, "{stats:?}");
+        assert!(stats.bytes_allocated >= 4096, "{stats:?}");
+        assert!(stats.peak_live_bytes >= 4096, "{stats:?}");
+    }
+}
@@ -0,0 +1,262 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Height of the letters that some puzzles render as their answer.
+const LETTER_HEIGHT: usize = 6;
+/// Width of a rendered letter, followed by one empty column between the letters.
+const LETTER_WIDTH: usize = 4;
+
+/// The letters known from previous events, drawn in the 4x6 font used by the puzzles.
+const LETTERS: [(char, [&str; LETTER_HEIGHT]); 16] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Answer to a part of a puzzle, which is either a number or text.
+///
+/// Solvers can return anything that converts into an answer, e.g. any integer type, strings
+/// or grids of pixels that render letters, so that running, checking and submitting the answers
+/// works the same way for every day.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// Joins the items with the separator, for puzzles that expect a list as answer (e.g. `1,2,3`).
+    pub fn joined<I, T>(items: I, separator: &str) -> Answer
+    where
+        I: IntoIterator<Item = T>,
+        T: Display,
+    {
+        let items: Vec<String> = items.into_iter().map(|i| i.to_string()).collect();
+        Answer::Text(items.join(separator))
+    }
+
+    /// Whether the answer spans multiple lines, like a grid whose letters could not be recognized.
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Text(text) if text.contains('\n'))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    /// Parses numbers as [`Answer::Number`] and everything else as [`Answer::Text`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(s.parse()
+            .map(Answer::Number)
+            .unwrap_or_else(|_| Answer::Text(s.to_string())))
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n)
+            .map(Answer::Number)
+            .unwrap_or_else(|_| Answer::Text(n.to_string()))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(c: char) -> Self {
+        Answer::Text(c.to_string())
+    }
+}
+
+impl From<Vec<Vec<char>>> for Answer {
+    fn from(grid: Vec<Vec<char>>) -> Self {
+        let lines: Vec<String> = grid.iter().map(|row| row.iter().collect()).collect();
+        Answer::Text(lines.join("\n"))
+    }
+}
+
+/// Grids of lit pixels are read as letters, if they are drawn in the known font.
+/// Otherwise the grid is kept as text so that it can be read by a human.
+impl From<Vec<Vec<bool>>> for Answer {
+    fn from(grid: Vec<Vec<bool>>) -> Self {
+        let rows: Vec<String> = grid
+            .iter()
+            .map(|row| row.iter().map(|lit| if *lit { '#' } else { '.' }).collect())
+            .collect();
+
+        match recognize_letters(&rows) {
+            Some(letters) => Answer::Text(letters),
+            None => Answer::Text(rows.join("\n")),
+        }
+    }
+}
+
+/// Reads the answers of both parts back from the output of a day binary, as printed by [`crate::run`].
+pub fn parse_output(output: &str) -> (Option<Answer>, Option<Answer>) {
+    let mut answers: [Option<Vec<&str>>; 2] = [None, None];
+    let mut current = None;
+
+    for line in output.lines() {
+        let part = match line.split_once(':') {
+            Some(("A", rest)) => Some((0, rest)),
+            Some(("B", rest)) => Some((1, rest)),
+            _ => None,
+        };
+
+        match (part, current) {
+            (Some((index, rest)), _) => {
+                let rest = rest.trim();
+                answers[index] = Some(if rest.is_empty() { vec![] } else { vec![rest] });
+                current = Some(index);
+            }
+            (None, Some(index)) => answers[index].as_mut().unwrap().push(line),
+            (None, None) => {}
+        }
+    }
+
+    let [a, b] = answers.map(|lines| lines.map(|lines| lines.join("\n").parse().unwrap()));
+    (a, b)
+}
+
+fn recognize_letters(rows: &[String]) -> Option<String> {
+    if rows.len() != LETTER_HEIGHT {
+        return None;
+    }
+
+    let width = rows.iter().map(|row| row.len()).max()?;
+    let letter_count = width.div_ceil(LETTER_WIDTH + 1);
+    if letter_count == 0 {
+        return None;
+    }
+
+    (0..letter_count)
+        .map(|i| {
+            let start = i * (LETTER_WIDTH + 1);
+            let glyph: Vec<String> = rows
+                .iter()
+                .map(|row| {
+                    let column: String = row.chars().skip(start).take(LETTER_WIDTH).collect();
+                    format!("{column:.<LETTER_WIDTH$}")
+                })
+                .collect();
+
+            LETTERS
+                .iter()
+                .find(|(_, pattern)| pattern.iter().zip(glyph.iter()).all(|(p, g)| p == g))
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_output, Answer};
+
+    fn pixels(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_from_integers() {
+        assert_eq!(Answer::from(42u8), Answer::Number(42));
+        assert_eq!(Answer::from(-3i64), Answer::Number(-3));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1234\n".parse::<Answer>().unwrap(), Answer::Number(1234));
+        assert_eq!(
+            "1,2,3".parse::<Answer>().unwrap(),
+            Answer::Text("1,2,3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_joined() {
+        assert_eq!(Answer::joined([4, 6, 3], ",").to_string(), "4,6,3");
+    }
+
+    #[test]
+    fn test_letters_from_grid() {
+        let grid = pixels(&[
+            "#..#.####.#....#.....##.",
+            "#..#.#....#....#....#..#",
+            "####.###..#....#....#..#",
+            "#..#.#....#....#....#..#",
+            "#..#.#....#....#....#..#",
+            "#..#.####.####.####..##.",
+        ]);
+        assert_eq!(Answer::from(grid), Answer::Text("HELLO".to_string()));
+    }
+
+    #[test]
+    fn test_unknown_grid_stays_text() {
+        let answer = Answer::from(pixels(&["#.", ".#"]));
+        assert_eq!(answer, Answer::Text("#.\n.#".to_string()));
+        assert!(answer.is_multiline());
+    }
+
+    #[test]
+    fn test_empty_grid_stays_text() {
+        let answer = Answer::from(pixels(&["", "", "", "", "", ""]));
+        assert_eq!(answer, Answer::Text("\n\n\n\n\n".to_string()));
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let (a, b) = parse_output("A: 42\nB:\n#.\n.#\n");
+        assert_eq!(a, Some(Answer::Number(42)));
+        assert_eq!(b, Some(Answer::Text("#.\n.#".to_string())));
+        assert_eq!(parse_output("A: 1,2\n"), (Some(Answer::from("1,2")), None));
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+use crate::PuzzleInput;
+
+/// Runs both parts on the real puzzle input of the given day and panics with the measured
+/// times if they took longer than the time budget of the day together.
+///
+/// Debug builds are way slower than release builds, so this is meant to be called from
+/// tests that only run in release mode (`cargo test --release`).
+pub fn assert_within_time_budget<A, B>(
+    year: u16,
+    day: u8,
+    budget: Duration,
+    solve_a: impl Fn(&PuzzleInput) -> A,
+    solve_b: impl Fn(&PuzzleInput) -> B,
+) {
+    let input = PuzzleInput::get_input(year, day);
+    check_time_budget(day, &input, budget, solve_a, solve_b);
+}
+
+fn check_time_budget<A, B>(
+    day: u8,
+    input: &PuzzleInput,
+    budget: Duration,
+    solve_a: impl Fn(&PuzzleInput) -> A,
+    solve_b: impl Fn(&PuzzleInput) -> B,
+) {
+    let start = Instant::now();
+    solve_a(input);
+    let time_a = start.elapsed();
+
+    let start = Instant::now();
+    solve_b(input);
+    let time_b = start.elapsed();
+
+    let total = time_a + time_b;
+    assert!(
+        total <= budget,
+        "Day {day:02} took {total:.2?} (A: {time_a:.2?}, B: {time_b:.2?}), which exceeds its time budget of {budget:.2?}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::check_time_budget;
+    use crate::PuzzleInput;
+
+    #[test]
+    fn test_within_budget() {
+        let input = PuzzleInput::new("");
+        check_time_budget(0, &input, Duration::from_secs(1), |_| 1, |_| 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds its time budget")]
+    fn test_budget_exceeded() {
+        check_time_budget(
+            0,
+            &PuzzleInput::new(""),
+            Duration::from_millis(1),
+            |_| std::thread::sleep(Duration::from_millis(5)),
+            |_| (),
+        );
+    }
+}
@@ -0,0 +1,50 @@
+/// Generates the standard tests of a day, to be invoked inside its `tests` module.
+///
+/// The first row generates `test_no_panic`, which runs the listed parts on the real input, and
+/// `test_time_budget`, which checks both parts against the time budget in release mode.
+/// Each following row generates a test with the given name, which checks the answer of a part
+/// for an example input:
+///
+/// ```ignore
+/// aoc_utils::day_tests! {
+///     real_input(YEAR, DAY, TIME_BUDGET): solve_a, solve_b;
+///     test_solve_a: TEST_INPUT, solve_a => 11;
+///     test_solve_b: TEST_INPUT, solve_b => 31;
+/// }
+/// ```
+///
+/// Parts that are too slow to run in debug mode can be left out of the `real_input` row,
+/// they are still run by `test_time_budget`. The functions `solve_a` and `solve_b` need to be in scope.
+#[macro_export]
+macro_rules! day_tests {
+    (
+        real_input($year:expr, $day:expr, $budget:expr): $($real_part:ident),+;
+        $($name:ident: $example:expr, $part:ident => $expected:expr;)*
+    ) => {
+        #[test]
+        fn test_no_panic() {
+            let input = $crate::PuzzleInput::get_input($year, $day);
+            $($real_part(&input);)+
+        }
+
+        #[test]
+        #[cfg_attr(debug_assertions, ignore = "time budgets are only checked in release mode")]
+        fn test_time_budget() {
+            $crate::budget::assert_within_time_budget($year, $day, $budget, solve_a, solve_b);
+        }
+
+        $(
+            #[test]
+            fn $name() {
+                let answer = $part(&$crate::PuzzleInput::new($example));
+                assert_eq!(
+                    $crate::Answer::from(answer),
+                    $crate::Answer::from($expected),
+                    "{} returned the wrong answer for the example {}",
+                    stringify!($part),
+                    stringify!($example)
+                );
+            }
+        )*
+    };
+}
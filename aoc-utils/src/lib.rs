@@ -1,6 +1,20 @@
+pub mod alloc_stats;
+pub mod answer;
+pub mod budget;
+mod day_tests;
+pub mod logging;
+pub mod progress;
+
 use std::fs;
 use std::iter::Map;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+pub use answer::Answer;
+
+/// Environment variable selecting the profile, i.e. the Advent of Code account whose puzzle inputs
+/// are used. Every account has different inputs, so solutions can be checked against several of them.
+pub const PROFILE_VAR: &str = "AOC_PROFILE";
 
 pub struct PuzzleInput {
     pub raw_input: String,
@@ -13,33 +27,36 @@ impl PuzzleInput {
         }
     }
 
-    /// Reads the puzzle input for the puzzle with the given day from input_day<day_number>.txt
-    pub fn get_input(day: u8) -> PuzzleInput {
-        let path_string = format!("input_day{:02}.txt", day);
+    /// Reads the puzzle input for the puzzle of the given year and day from input_day<day_number>.txt,
+    /// or the input of the selected profile.
+    pub fn get_input(year: u16, day: u8) -> PuzzleInput {
+        let path_string = input_file_name(day);
         let path = Path::new(&path_string);
 
         // When running in tests, the working directory is inside the package, but
         // when running the actual puzzle, it's in the workspace root.
-        let outside_path_str = format!("day{:02}/{}", day, path.display());
-        let outside_path = Path::new(&outside_path_str);
+        let outside_path = day_dir(year, day).join(path);
+        // The day00 template is located directly in the workspace root instead of a year directory.
+        let template_path = Path::new(&format!("day{:02}", day)).join(path);
 
-        if path.exists() {
+        let existing_path = [path, &outside_path, &template_path]
+            .into_iter()
+            .find(|p| p.exists());
+
+        if let Some(path) = existing_path {
             let content = fs::read_to_string(path).expect("Unable to read file");
             PuzzleInput::new(content)
-        } else if outside_path.exists() {
-            let content = fs::read_to_string(outside_path).expect("Unable to read file");
-            PuzzleInput::new(content)
         } else {
             let cwd = std::env::current_dir().unwrap();
             panic!(
                 "Puzzle input at {}/{} does not exist",
                 cwd.display(),
-                path.display()
+                outside_path.display()
             );
         }
     }
 
-    pub fn lines(&self) -> Map<std::str::Lines, fn(&str) -> String> {
+    pub fn lines(&self) -> Map<std::str::Lines<'_>, fn(&str) -> String> {
         self.raw_input.lines().map(|s| s.to_string())
     }
 
@@ -58,12 +75,102 @@ impl PuzzleInput {
     }
 }
 
+/// The profile selected using `AOC_PROFILE`, `None` for the default account.
+pub fn profile() -> Option<String> {
+    std::env::var(PROFILE_VAR)
+        .ok()
+        .filter(|profile| !profile.is_empty())
+        .map(|profile| parse_profile(&profile).unwrap_or_else(|e| panic!("{PROFILE_VAR}: {e}")))
+}
+
+/// Validates a profile name and normalizes it to lowercase. The name becomes part of file and
+/// environment variable names, so only ASCII letters, digits, `_` and `-` are allowed.
+pub fn parse_profile(name: &str) -> Result<String, String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(name.to_ascii_lowercase())
+    } else {
+        Err(format!(
+            "invalid profile name {name:?}, only letters, digits, '_' and '-' are allowed"
+        ))
+    }
+}
+
+/// Name of a file that exists once per profile, e.g. `input_day01.txt` for the default account
+/// and `input_day01.work.txt` for the profile `work`.
+pub fn profile_file_name(stem: &str, extension: &str) -> String {
+    file_name(stem, extension, profile().as_deref())
+}
+
+fn file_name(stem: &str, extension: &str, profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => format!("{stem}.{profile}.{extension}"),
+        None => format!("{stem}.{extension}"),
+    }
+}
+
+pub fn input_file_name(day: u8) -> String {
+    profile_file_name(&format!("input_day{day:02}"), "txt")
+}
+
+/// Directory of the crate of a day relative to the workspace root, e.g. `y2024/day01`.
+pub fn day_dir(year: u16, day: u8) -> PathBuf {
+    Path::new(&format!("y{year}")).join(format!("day{day:02}"))
+}
+
+/// Name of the package of a day, e.g. `y2024-day01`.
+pub fn day_package_name(year: u16, day: u8) -> String {
+    format!("y{year}-day{day:02}")
+}
+
+/// Entry point of the day binaries, which solves both parts for the puzzle input of the given day
+/// and prints the answers.
+pub fn run<A: Into<Answer>, B: Into<Answer>>(
+    year: u16,
+    day: u8,
+    solve_a: impl Fn(&PuzzleInput) -> A,
+    solve_b: impl Fn(&PuzzleInput) -> B,
+) {
+    logging::init();
+    progress::enable();
+
+    let input = run_phase("parse", || PuzzleInput::get_input(year, day));
+    let answer_a = run_phase("A", || solve_a(&input).into());
+    print_answer("A", answer_a);
+    let answer_b = run_phase("B", || solve_b(&input).into());
+    print_answer("B", answer_b);
+}
+
+/// Runs a phase of the solution and reports its time and allocations to stderr,
+/// if the allocations are counted.
+fn run_phase<T>(name: &str, phase: impl FnOnce() -> T) -> T {
+    if !alloc_stats::is_enabled() {
+        return phase();
+    }
+
+    let start = Instant::now();
+    let (result, stats) = alloc_stats::measure(phase);
+    eprintln!("{name}: {:.2?}, {stats}", start.elapsed());
+    result
+}
+
+fn print_answer(part: &str, answer: Answer) {
+    if answer.is_multiline() {
+        println!("{part}:\n{answer}");
+    } else {
+        println!("{part}: {answer}");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // Should be able to get input "Hello, this is a test" from day 0
     #[test]
     fn test_get_input_0_success() {
-        let input = super::PuzzleInput::get_input(0);
+        let input = super::PuzzleInput::get_input(2024, 0);
         assert_eq!(input.raw_input, "Hello, this is a test\n");
     }
 
@@ -71,6 +178,40 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_get_input_1b_fail() {
-        super::PuzzleInput::get_input(1);
+        super::PuzzleInput::get_input(2024, 1);
+    }
+
+    #[test]
+    fn test_day_layout() {
+        assert_eq!(
+            super::day_dir(2024, 1),
+            std::path::Path::new("y2024").join("day01")
+        );
+        assert_eq!(super::day_package_name(2023, 25), "y2023-day25");
+    }
+
+    #[test]
+    fn test_profile_file_name() {
+        assert_eq!(
+            super::file_name("input_day01", "txt", None),
+            "input_day01.txt"
+        );
+        assert_eq!(
+            super::file_name("input_day01", "txt", Some("work")),
+            "input_day01.work.txt"
+        );
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(super::parse_profile("Work").unwrap(), "work");
+        assert_eq!(
+            super::parse_profile("second_account-2").unwrap(),
+            "second_account-2"
+        );
+        assert!(super::parse_profile("").is_err());
+        assert!(super::parse_profile("../../x").is_err());
+        assert!(super::parse_profile("a/b").is_err());
+        assert!(super::parse_profile("a.b").is_err());
     }
 }
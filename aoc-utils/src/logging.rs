@@ -0,0 +1,90 @@
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Environment variable that sets the log level (e.g. `AOC_LOG=debug`) and takes
+/// precedence over the `-v` flags.
+pub const LOG_ENV_VAR: &str = "AOC_LOG";
+
+/// Logs everything to stderr, so that the answers on stdout stay clean.
+struct StderrLogger {
+    level: LevelFilter,
+}
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "[{:<5} {}] {}",
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the stderr logger with the level from the `AOC_LOG` environment variable, or from the
+/// number of `-v` flags in the command line arguments if it isn't set.
+/// Without either only warnings and errors are logged, `-v` enables debug and `-vv` trace output.
+pub fn init() {
+    let level = std::env::var(LOG_ENV_VAR)
+        .ok()
+        .and_then(|level| level.parse().ok())
+        .unwrap_or_else(|| level_from_verbosity(verbosity_from_args(std::env::args().skip(1))));
+
+    if log::set_boxed_logger(Box::new(StderrLogger { level })).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+fn verbosity_from_args<I: IntoIterator<Item = String>>(args: I) -> usize {
+    args.into_iter()
+        .map(|arg| match arg.as_str() {
+            "--verbose" => 1,
+            flags if flags.len() > 1 && flags.starts_with('-') && !flags.starts_with("--") => {
+                flags.chars().skip(1).filter(|c| *c == 'v').count()
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+fn level_from_verbosity(verbosity: usize) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use log::LevelFilter;
+
+    use super::{level_from_verbosity, verbosity_from_args};
+
+    fn verbosity(args: &[&str]) -> usize {
+        verbosity_from_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_verbosity_from_args() {
+        assert_eq!(verbosity(&[]), 0);
+        assert_eq!(verbosity(&["-v"]), 1);
+        assert_eq!(verbosity(&["-vv"]), 2);
+        assert_eq!(verbosity(&["-v", "--verbose"]), 2);
+        assert_eq!(verbosity(&["input.txt", "--version"]), 0);
+    }
+
+    #[test]
+    fn test_level_from_verbosity() {
+        assert_eq!(level_from_verbosity(0), LevelFilter::Warn);
+        assert_eq!(level_from_verbosity(1), LevelFilter::Debug);
+        assert_eq!(level_from_verbosity(3), LevelFilter::Trace);
+    }
+}
@@ -0,0 +1,118 @@
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+const BAR_WIDTH: u64 = 30;
+
+/// Progress bars are only drawn once a binary has opted in, so tests never render anything.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Allows progress bars to be rendered to stderr, if it is connected to a terminal.
+/// Called by the entry point of the day binaries.
+pub fn enable() {
+    ENABLED.store(std::io::stderr().is_terminal(), Ordering::Relaxed);
+}
+
+/// Progress of a long running computation, that can be shared between threads.
+///
+/// The progress is rendered as a bar to stderr when running interactively and
+/// is silent in tests and when the output is redirected.
+pub struct Progress {
+    total: u64,
+    current: AtomicU64,
+    message: Mutex<String>,
+    /// Last rendered percentage, to only redraw when something visible has changed.
+    rendered_percent: AtomicU64,
+    visible: bool,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Progress {
+        Progress {
+            total: total as u64,
+            current: AtomicU64::new(0),
+            message: Mutex::new(String::new()),
+            rendered_percent: AtomicU64::new(u64::MAX),
+            visible: ENABLED.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn increment(&self) {
+        self.increment_by(1);
+    }
+
+    pub fn increment_by(&self, amount: usize) {
+        let current = self.current.fetch_add(amount as u64, Ordering::Relaxed) + amount as u64;
+        let percent = self.percent(current);
+        if self.rendered_percent.swap(percent, Ordering::Relaxed) != percent {
+            self.render();
+        }
+    }
+
+    pub fn set_message<S: Into<String>>(&self, message: S) {
+        *self.message.lock().unwrap() = message.into();
+        self.render();
+    }
+
+    pub fn position(&self) -> usize {
+        self.current.load(Ordering::Relaxed) as usize
+    }
+
+    fn percent(&self, current: u64) -> u64 {
+        (current.min(self.total) * 100)
+            .checked_div(self.total)
+            .unwrap_or(100)
+    }
+
+    fn render(&self) {
+        if !self.visible {
+            return;
+        }
+
+        let current = self.current.load(Ordering::Relaxed).min(self.total);
+        let filled = (current * BAR_WIDTH)
+            .checked_div(self.total)
+            .unwrap_or(BAR_WIDTH);
+        let bar = "#".repeat(filled as usize) + &"-".repeat((BAR_WIDTH - filled) as usize);
+        let message = self.message.lock().unwrap();
+
+        let mut stderr = io::stderr().lock();
+        let _ = write!(
+            stderr,
+            "\r\x1b[2K[{bar}] {current}/{} ({}%) {message}",
+            self.total,
+            self.percent(current)
+        );
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.visible {
+            // Clear the bar so that it doesn't mix with the following output.
+            let _ = write!(io::stderr(), "\r\x1b[2K");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Progress;
+
+    #[test]
+    fn test_progress_counts_across_threads() {
+        let progress = Progress::new(100);
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..25 {
+                        progress.increment();
+                    }
+                });
+            }
+        });
+        progress.set_message("done");
+        assert_eq!(progress.position(), 100);
+    }
+}
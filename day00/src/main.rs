@@ -1,10 +1,10 @@
+//! Day 0
 use aoc_utils::PuzzleInput;
+const YEAR: u16 = 2024;
 const DAY: u8 = 0;
 
 fn main() {
-    let input = PuzzleInput::get_input(DAY);
-    println!("A: {}", solve_a(&input));
-    println!("B: {}", solve_b(&input));
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
 }
 
 fn solve_a(input: &PuzzleInput) -> usize {
@@ -18,23 +18,15 @@ fn solve_b(input: &PuzzleInput) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
-    const TEST_INPUT: &str = "";
-
-    #[test]
-    fn test_no_panic() {
-        let input = PuzzleInput::get_input(DAY);
-        solve_a(&input);
-        solve_b(&input);
-    }
+    const TIME_BUDGET: Duration = Duration::from_millis(100);
 
-    #[test]
-    fn test_solve_a() {
-        assert_eq!(solve_a(&PuzzleInput::new(TEST_INPUT)), 0);
-    }
+    const TEST_INPUT: &str = "";
 
-    #[test]
-    fn test_solve_b() {
-        assert_eq!(solve_b(&PuzzleInput::new(TEST_INPUT)), 0);
+    aoc_utils::day_tests! {
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a, solve_b;
+        test_solve_a: TEST_INPUT, solve_a => 0;
+        test_solve_b: TEST_INPUT, solve_b => 0;
     }
 }
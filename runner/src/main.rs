@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_utils::{day_dir, day_package_name};
+use clap::{ArgAction, Parser};
+
+mod process;
+
+use process::DayRun;
+
+// This is a utility that builds the solutions of all days in release mode and runs them
+// concurrently on a pool of worker threads. The answers are still printed in day order
+// together with the wall-clock and CPU time each day needed.
+
+/// A day of a specific event, which has a crate in the y<year>/day<day> directory.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct PuzzleDay {
+    year: u16,
+    day: u8,
+}
+
+impl std::fmt::Display for PuzzleDay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {:02}", self.year, self.day)
+    }
+}
+
+/// Runs the solutions of multiple days concurrently.
+#[derive(Parser)]
+struct Args {
+    /// Days to run. Runs every day that has a crate if none are given.
+    days: Vec<u8>,
+
+    /// Only runs the days of this year. Runs the days of all years if not given.
+    #[arg(short, long)]
+    year: Option<u16>,
+
+    /// Number of days that are run at the same time.
+    #[arg(short, long, default_value_t = default_jobs())]
+    jobs: usize,
+
+    /// Counts the allocations of each day and reports them next to the time of each part.
+    #[arg(long)]
+    alloc: bool,
+
+    /// Passes -v to the days, which enables debug output, or trace output when given twice.
+    #[arg(short, long, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+fn default_jobs() -> usize {
+    thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1)
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let workspace_root = workspace_root();
+
+    let available: Vec<PuzzleDay> = find_days(&workspace_root)
+        .into_iter()
+        .filter(|d| args.year.is_none_or(|year| d.year == year))
+        .collect();
+    let missing = missing_days(&args.days, &available);
+    if !missing.is_empty() {
+        let missing: Vec<String> = missing.iter().map(|day| format!("{day:02}")).collect();
+        eprintln!("No crate found for day {}", missing.join(", "));
+        return ExitCode::FAILURE;
+    }
+
+    let days: Vec<PuzzleDay> = available
+        .into_iter()
+        .filter(|d| args.days.is_empty() || args.days.contains(&d.day))
+        .collect();
+    if days.is_empty() {
+        eprintln!("No days found to run");
+        return ExitCode::FAILURE;
+    }
+
+    if !build_days(&days, args.alloc) {
+        eprintln!("Building the days failed");
+        return ExitCode::FAILURE;
+    }
+
+    let start = Instant::now();
+    let mut all_succeeded = true;
+    let mut total_cpu_time = Duration::ZERO;
+
+    let day_args: Vec<String> = match args.verbose {
+        0 => vec![],
+        verbose => vec![format!("-{}", "v".repeat(verbose as usize))],
+    };
+
+    for (day, result) in run_days(days.clone(), args.jobs.max(1), &workspace_root, &day_args) {
+        match result {
+            Ok(run) => {
+                all_succeeded &= run.status.success();
+                total_cpu_time += run.cpu_time.unwrap_or_default();
+                print_day(day, &run);
+            }
+            Err(err) => {
+                all_succeeded = false;
+                println!("{day}: could not be started: {err}");
+            }
+        }
+    }
+
+    println!(
+        "Ran {} days with {} jobs in {:.2?} wall-clock, {:.2?} CPU time",
+        days.len(),
+        args.jobs.max(1),
+        start.elapsed(),
+        total_cpu_time
+    );
+
+    if all_succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Requested days that have no crate in any of the available years.
+fn missing_days(requested: &[u8], available: &[PuzzleDay]) -> Vec<u8> {
+    let mut missing: Vec<u8> = requested
+        .iter()
+        .copied()
+        .filter(|day| !available.iter().any(|d| d.day == *day))
+        .collect();
+    missing.sort_unstable();
+    missing.dedup();
+    missing
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner crate is inside the workspace")
+        .to_path_buf()
+}
+
+/// Returns all days that have a crate in one of the y<year> directories of the workspace.
+fn find_days(workspace_root: &Path) -> Vec<PuzzleDay> {
+    let mut days: Vec<PuzzleDay> = fs::read_dir(workspace_root)
+        .expect("Could not read workspace directory")
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix('y')?.parse().ok()
+        })
+        .flat_map(|year| (1..=25).map(move |day| PuzzleDay { year, day }))
+        .filter(|d| {
+            workspace_root
+                .join(day_dir(d.year, d.day))
+                .join("Cargo.toml")
+                .exists()
+        })
+        .collect();
+    days.sort();
+    days
+}
+
+fn build_days(days: &[PuzzleDay], count_allocations: bool) -> bool {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command.args(["build", "--release"]);
+    if count_allocations {
+        command.args(["--features", "aoc-utils/alloc-stats"]);
+    }
+    for d in days {
+        command.arg("-p").arg(day_package_name(d.year, d.day));
+    }
+
+    command.status().is_ok_and(|status| status.success())
+}
+
+fn binary_path(workspace_root: &Path, day: PuzzleDay) -> PathBuf {
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_root.join("target"));
+    target_dir.join("release").join(format!(
+        "{}{}",
+        day_package_name(day.year, day.day),
+        std::env::consts::EXE_SUFFIX
+    ))
+}
+
+/// Runs the days on `jobs` worker threads and yields the results in the order of `days`,
+/// as soon as all earlier days have finished as well.
+fn run_days(
+    days: Vec<PuzzleDay>,
+    jobs: usize,
+    workspace_root: &Path,
+    day_args: &[String],
+) -> impl Iterator<Item = (PuzzleDay, std::io::Result<DayRun>)> {
+    let queue = Arc::new(Mutex::new(days.into_iter().enumerate()));
+    let (sender, receiver) = mpsc::channel();
+
+    for _ in 0..jobs {
+        let queue = Arc::clone(&queue);
+        let sender = sender.clone();
+        let workspace_root = workspace_root.to_path_buf();
+        let day_args = day_args.to_vec();
+        thread::spawn(move || loop {
+            let Some((index, day)) = queue.lock().unwrap().next() else {
+                break;
+            };
+            let binary = binary_path(&workspace_root, day);
+            let result = process::run_day(&binary, &workspace_root, &day_args);
+            if sender.send((index, (day, result))).is_err() {
+                break;
+            }
+        });
+    }
+    drop(sender);
+
+    in_order(receiver.into_iter())
+}
+
+/// Reorders items that arrive out of order, tagged with their index starting at 0. Each item is
+/// yielded as soon as all items with a lower index have been yielded.
+fn in_order<T>(finished: impl Iterator<Item = (usize, T)>) -> impl Iterator<Item = T> {
+    let mut finished = finished;
+    let mut pending = BTreeMap::new();
+    let mut next_index = 0;
+    std::iter::from_fn(move || loop {
+        if let Some(item) = pending.remove(&next_index) {
+            next_index += 1;
+            return Some(item);
+        }
+
+        let (index, item) = finished.next()?;
+        pending.insert(index, item);
+    })
+}
+
+fn print_day(day: PuzzleDay, run: &DayRun) {
+    let cpu_time = match run.cpu_time {
+        Some(cpu_time) => format!("{cpu_time:.2?}"),
+        None => "n/a".to_string(),
+    };
+    println!(
+        "{day} ({:.2?} wall-clock, {cpu_time} CPU time)",
+        run.wall_time
+    );
+    print!("{}", run.stdout);
+    // Forwarded to stderr so only answers end up on stdout. Contains the log output of the day
+    // when running verbosely, the allocation stats and the panic message if it failed.
+    eprint!("{}", run.stderr);
+
+    if !run.status.success() {
+        println!("{day} failed with {}", run.status);
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_days() {
+        let available = [
+            PuzzleDay { year: 2024, day: 1 },
+            PuzzleDay { year: 2024, day: 2 },
+        ];
+        assert_eq!(missing_days(&[], &available), Vec::<u8>::new());
+        assert_eq!(missing_days(&[2, 1], &available), Vec::<u8>::new());
+        assert_eq!(missing_days(&[8, 1, 3, 8], &available), [3, 8]);
+    }
+
+    #[test]
+    fn test_in_order() {
+        // The later days finish first, e.g. because the first day is the slowest.
+        let finished = [(2, "day 3"), (1, "day 2"), (3, "day 4"), (0, "day 1")];
+        let mut ordered = in_order(finished.into_iter());
+        assert_eq!(ordered.next(), Some("day 1"));
+        assert_eq!(ordered.collect::<Vec<_>>(), ["day 2", "day 3", "day 4"]);
+    }
+
+    #[test]
+    fn test_in_order_yields_finished_days_right_away() {
+        let mut seen = Vec::new();
+        let finished = [(1, "day 2"), (0, "day 1"), (2, "day 3")]
+            .into_iter()
+            .inspect(|(index, _)| seen.push(*index));
+        let first = in_order(finished).next();
+        assert_eq!(first, Some("day 1"));
+        // Day 1 is yielded before day 3 finished.
+        assert_eq!(seen, [1, 0]);
+    }
+}
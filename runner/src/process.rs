@@ -0,0 +1,87 @@
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Output and resource usage of a single finished day binary.
+pub struct DayRun {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+    pub wall_time: Duration,
+    /// User + system CPU time of the process, if the platform can report it.
+    pub cpu_time: Option<Duration>,
+}
+
+/// Runs the day binary with the workspace root as working directory and collects its output.
+pub fn run_day(binary: &Path, workspace_root: &Path, args: &[String]) -> io::Result<DayRun> {
+    let start = Instant::now();
+    let mut child = Command::new(binary)
+        .args(args)
+        .current_dir(workspace_root)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Both pipes have to be drained concurrently, otherwise the child can block on a full stderr pipe
+    // while we are still waiting for stdout to be closed.
+    let mut stderr_pipe = child.stderr.take().unwrap();
+    let stderr_reader = thread::spawn(move || {
+        let mut stderr = String::new();
+        stderr_pipe.read_to_string(&mut stderr).map(|_| stderr)
+    });
+
+    let mut stdout = String::new();
+    child.stdout.take().unwrap().read_to_string(&mut stdout)?;
+
+    let (status, cpu_time) = wait(&mut child)?;
+    let wall_time = start.elapsed();
+    let stderr = stderr_reader.join().expect("stderr reader panicked")?;
+
+    Ok(DayRun {
+        status,
+        stdout,
+        stderr,
+        wall_time,
+        cpu_time,
+    })
+}
+
+#[cfg(unix)]
+fn wait(child: &mut Child) -> io::Result<(ExitStatus, Option<Duration>)> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let pid = child.id() as libc::pid_t;
+    let mut status = 0;
+    // SAFETY: rusage is a plain C struct for which all zeroes is a valid value.
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+
+    // wait4 reaps exactly this child and reports its own resource usage, unlike
+    // getrusage(RUSAGE_CHILDREN) which would mix up the days running in parallel.
+    loop {
+        // SAFETY: pid belongs to our not yet reaped child and both pointers are valid for the call.
+        let result = unsafe { libc::wait4(pid, &mut status, 0, &mut usage) };
+        if result >= 0 {
+            break;
+        }
+
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+
+    let cpu_time = timeval_to_duration(usage.ru_utime) + timeval_to_duration(usage.ru_stime);
+    Ok((ExitStatus::from_raw(status), Some(cpu_time)))
+}
+
+#[cfg(not(unix))]
+fn wait(child: &mut Child) -> io::Result<(ExitStatus, Option<Duration>)> {
+    Ok((child.wait()?, None))
+}
+
+#[cfg(unix)]
+fn timeval_to_duration(time: libc::timeval) -> Duration {
+    Duration::from_secs(time.tv_sec as u64) + Duration::from_micros(time.tv_usec as u64)
+}
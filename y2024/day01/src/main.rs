@@ -1,4 +1,5 @@
 use aoc_utils::PuzzleInput;
+const YEAR: u16 = 2024;
 const DAY: u8 = 1;
 
 struct ListPair {
@@ -31,7 +32,7 @@ impl ListPair {
         self.left
             .iter()
             .zip(self.right.iter())
-            .map(|(a, b)| if a > b { a - b } else { b - a })
+            .map(|(a, b)| a.abs_diff(*b))
             .sum()
     }
 
@@ -47,9 +48,7 @@ impl ListPair {
 }
 
 fn main() {
-    let input = PuzzleInput::get_input(DAY);
-    println!("A: {}", solve_a(&input));
-    println!("B: {}", solve_b(&input));
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
 }
 
 fn solve_a(input: &PuzzleInput) -> u64 {
@@ -63,6 +62,9 @@ fn solve_b(input: &PuzzleInput) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+
+    const TIME_BUDGET: Duration = Duration::from_millis(100);
 
     const TEST_INPUT: &str = "3   4
 4   3
@@ -72,20 +74,9 @@ mod tests {
 3   3
 ";
 
-    #[test]
-    fn test_no_panic() {
-        let input = PuzzleInput::get_input(DAY);
-        solve_a(&input);
-        solve_b(&input);
-    }
-
-    #[test]
-    fn test_solve_a() {
-        assert_eq!(solve_a(&PuzzleInput::new(TEST_INPUT)), 11);
-    }
-
-    #[test]
-    fn test_solve_b() {
-        assert_eq!(solve_b(&PuzzleInput::new(TEST_INPUT)), 31);
+    aoc_utils::day_tests! {
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a, solve_b;
+        test_solve_a: TEST_INPUT, solve_a => 11;
+        test_solve_b: TEST_INPUT, solve_b => 31;
     }
 }
@@ -1,10 +1,9 @@
 use aoc_utils::PuzzleInput;
+const YEAR: u16 = 2024;
 const DAY: u8 = 2;
 
 fn main() {
-    let input = PuzzleInput::get_input(DAY);
-    println!("A: {}", solve_a(&input));
-    println!("B: {}", solve_b(&input));
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
 }
 
 fn parse_input(input: &PuzzleInput) -> Vec<Vec<usize>> {
@@ -73,6 +72,9 @@ fn solve_b(input: &PuzzleInput) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+
+    const TIME_BUDGET: Duration = Duration::from_millis(100);
 
     const TEST_INPUT: &str = "7 6 4 2 1
 1 2 7 8 9
@@ -82,20 +84,9 @@ mod tests {
 1 3 6 7 9
 ";
 
-    #[test]
-    fn test_no_panic() {
-        let input = PuzzleInput::get_input(DAY);
-        solve_a(&input);
-        solve_b(&input);
-    }
-
-    #[test]
-    fn test_solve_a() {
-        assert_eq!(solve_a(&PuzzleInput::new(TEST_INPUT)), 2);
-    }
-
-    #[test]
-    fn test_solve_b() {
-        assert_eq!(solve_b(&PuzzleInput::new(TEST_INPUT)), 4);
+    aoc_utils::day_tests! {
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a, solve_b;
+        test_solve_a: TEST_INPUT, solve_a => 2;
+        test_solve_b: TEST_INPUT, solve_b => 4;
     }
 }
@@ -1,12 +1,11 @@
 use aoc_utils::PuzzleInput;
 use regex::Regex;
 
+const YEAR: u16 = 2024;
 const DAY: u8 = 3;
 
 fn main() {
-    let input = PuzzleInput::get_input(DAY);
-    println!("A: {}", solve_a(&input));
-    println!("B: {}", solve_b(&input));
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
 }
 
 fn calculate_score(input: &PuzzleInput, do_dont_enabled: bool) -> u32 {
@@ -39,26 +38,18 @@ fn solve_b(input: &PuzzleInput) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+
+    const TIME_BUDGET: Duration = Duration::from_millis(100);
 
     const TEST_INPUT_A: &str =
         "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
     const TEST_INPUT_B: &str =
         "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
-    #[test]
-    fn test_no_panic() {
-        let input = PuzzleInput::get_input(DAY);
-        solve_a(&input);
-        solve_b(&input);
-    }
-
-    #[test]
-    fn test_solve_a() {
-        assert_eq!(solve_a(&PuzzleInput::new(TEST_INPUT_A)), 161);
-    }
-
-    #[test]
-    fn test_solve_b() {
-        assert_eq!(solve_b(&PuzzleInput::new(TEST_INPUT_B)), 48);
+    aoc_utils::day_tests! {
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a, solve_b;
+        test_solve_a: TEST_INPUT_A, solve_a => 161;
+        test_solve_b: TEST_INPUT_B, solve_b => 48;
     }
 }
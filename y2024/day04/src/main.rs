@@ -1,10 +1,9 @@
 use aoc_utils::PuzzleInput;
+const YEAR: u16 = 2024;
 const DAY: u8 = 4;
 
 fn main() {
-    let input = PuzzleInput::get_input(DAY);
-    println!("A: {}", solve_a(&input));
-    println!("B: {}", solve_b(&input));
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
 }
 
 fn check_xmas(x: usize, y: usize, map: &[Vec<char>], offset: (i8, i8)) -> bool {
@@ -89,6 +88,9 @@ fn solve_b(input: &PuzzleInput) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+
+    const TIME_BUDGET: Duration = Duration::from_millis(100);
 
     const TEST_INPUT: &str = "MMMSXXMASM
 MSAMXMSMSA
@@ -101,20 +103,9 @@ SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX";
 
-    #[test]
-    fn test_no_panic() {
-        let input = PuzzleInput::get_input(DAY);
-        solve_a(&input);
-        solve_b(&input);
-    }
-
-    #[test]
-    fn test_solve_a() {
-        assert_eq!(solve_a(&PuzzleInput::new(TEST_INPUT)), 18);
-    }
-
-    #[test]
-    fn test_solve_b() {
-        assert_eq!(solve_b(&PuzzleInput::new(TEST_INPUT)), 9);
+    aoc_utils::day_tests! {
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a, solve_b;
+        test_solve_a: TEST_INPUT, solve_a => 18;
+        test_solve_b: TEST_INPUT, solve_b => 9;
     }
 }
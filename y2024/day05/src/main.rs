@@ -1,13 +1,12 @@
 use aoc_utils::PuzzleInput;
+const YEAR: u16 = 2024;
 const DAY: u8 = 5;
 
 fn main() {
-    let input = PuzzleInput::get_input(DAY);
-    println!("A: {}", solve_a(&input));
-    println!("B: {}", solve_b(&input));
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 struct PageOrderingRule {
     lower_page_num: usize,
     higher_page_num: usize,
@@ -92,14 +91,24 @@ fn parse_inputs(input: &PuzzleInput) -> (Vec<PageOrderingRule>, Vec<PageUpdate>)
         .unwrap()
         .split("\n")
         .map(PageOrderingRule::parse)
-        .collect();
+        .collect::<Vec<_>>();
     let page_updates = input_parts
         .next()
         .unwrap()
         .split("\n")
         .filter(|line| !line.is_empty())
         .map(PageUpdate::parse)
-        .collect();
+        .collect::<Vec<_>>();
+
+    log::debug!(
+        "Parsed {} ordering rules and {} page updates",
+        rules.len(),
+        page_updates.len()
+    );
+    for rule in rules.iter() {
+        log::trace!("{rule:?}");
+    }
+
     (rules, page_updates)
 }
 
@@ -130,6 +139,9 @@ fn solve_b(input: &PuzzleInput) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+
+    const TIME_BUDGET: Duration = Duration::from_millis(100);
 
     const TEST_INPUT: &str = "47|53
 97|13
@@ -160,20 +172,9 @@ mod tests {
 61,13,29
 97,13,75,29,47";
 
-    #[test]
-    fn test_no_panic() {
-        let input = PuzzleInput::get_input(DAY);
-        solve_a(&input);
-        solve_b(&input);
-    }
-
-    #[test]
-    fn test_solve_a() {
-        assert_eq!(solve_a(&PuzzleInput::new(TEST_INPUT)), 143);
-    }
-
-    #[test]
-    fn test_solve_b() {
-        assert_eq!(solve_b(&PuzzleInput::new(TEST_INPUT)), 123);
+    aoc_utils::day_tests! {
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a, solve_b;
+        test_solve_a: TEST_INPUT, solve_a => 143;
+        test_solve_b: TEST_INPUT, solve_b => 123;
     }
 }
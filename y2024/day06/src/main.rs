@@ -1,14 +1,14 @@
+use aoc_utils::progress::Progress;
 use aoc_utils::PuzzleInput;
 use std::collections::HashSet;
+const YEAR: u16 = 2024;
 const DAY: u8 = 6;
 
 fn main() {
-    let input = PuzzleInput::get_input(DAY);
-    println!("A: {}", solve_a(&input));
-    println!("B: {}", solve_b(&input));
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
 }
 
-#[derive(Eq, PartialEq, Clone)]
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
 enum Direction {
     Up,
     Right,
@@ -41,7 +41,6 @@ impl Direction {
     }
 }
 
-#[derive(Clone)]
 struct GuardMap {
     map: Vec<Vec<bool>>,
     guard_position: (usize, usize),
@@ -97,6 +96,11 @@ impl GuardMap {
 
     fn walk(&mut self) {
         self.guard_position = self.determine_next_position();
+        log::trace!(
+            "Guard at {:?} facing {:?}",
+            self.guard_position,
+            self.guard_direction
+        );
     }
 
     fn rotate_right_if_something_in_front(&mut self) -> bool {
@@ -111,7 +115,7 @@ impl GuardMap {
         false
     }
 
-    fn walk_till_out_of_map(&mut self) -> usize {
+    fn walk_till_out_of_map(&mut self) -> HashSet<(usize, usize)> {
         let mut visited_positions = HashSet::new();
         loop {
             if self.next_walk_out_of_map() {
@@ -123,60 +127,51 @@ impl GuardMap {
             visited_positions.insert(self.guard_position);
         }
 
-        visited_positions.len()
+        visited_positions
     }
 
     fn contains_loop(&mut self) -> bool {
-        let mut steps = vec![];
+        let mut steps = HashSet::new();
         loop {
             if self.next_walk_out_of_map() {
                 return false;
             }
 
             self.walk();
-            let step_pos = (
-                self.guard_position.0,
-                self.guard_position.1,
-                self.guard_direction.clone(),
-            );
-
-            if steps.contains(&step_pos) {
+            if !steps.insert((self.guard_position, self.guard_direction)) {
                 return true;
             }
 
-            steps.push(step_pos);
             while self.rotate_right_if_something_in_front() {
-                let step_pos = (
-                    self.guard_position.0,
-                    self.guard_position.1,
-                    self.guard_direction.clone(),
-                );
-
-                if steps.contains(&step_pos) {
+                if !steps.insert((self.guard_position, self.guard_direction)) {
                     return true;
                 }
-
-                steps.push(step_pos);
             }
         }
     }
 
     fn obstacle_positions_causing_loop(&mut self) -> usize {
-        let mut positions = 0;
-        for y in 0..self.map.len() {
-            println!("y: {y}/{}", self.map.len());
-            for x in 0..self.map[0].len() {
-                let pos = (x, y);
-                if pos == self.guard_position || self.map[y][x] {
-                    continue;
-                }
+        let start = (self.guard_position, self.guard_direction);
+        // An obstacle can only change the way of the guard if it is placed on that way.
+        let candidates: Vec<_> = self
+            .walk_till_out_of_map()
+            .into_iter()
+            .filter(|pos| *pos != start.0)
+            .collect();
 
-                let mut m = self.clone();
-                m.map[y][x] = true;
-                if m.contains_loop() {
-                    positions += 1
-                }
+        let mut positions = 0;
+        let progress = Progress::new(candidates.len());
+        for (x, y) in candidates {
+            // The map is reused instead of cloned for every candidate, so only the obstacle and
+            // the guard have to be reset afterwards.
+            self.map[y][x] = true;
+            (self.guard_position, self.guard_direction) = start;
+            if self.contains_loop() {
+                log::debug!("Obstacle at {:?} causes a loop", (x, y));
+                positions += 1
             }
+            self.map[y][x] = false;
+            progress.increment();
         }
 
         positions
@@ -185,7 +180,7 @@ impl GuardMap {
 
 fn solve_a(input: &PuzzleInput) -> usize {
     let mut map = GuardMap::parse(input);
-    map.walk_till_out_of_map()
+    map.walk_till_out_of_map().len()
 }
 
 fn solve_b(input: &PuzzleInput) -> usize {
@@ -196,6 +191,10 @@ fn solve_b(input: &PuzzleInput) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+
+    // Both parts take about 2.5s in release mode on an input with a 5400 cell path.
+    const TIME_BUDGET: Duration = Duration::from_secs(4);
 
     const TEST_INPUT: &str = "....#.....
 .........#
@@ -208,20 +207,10 @@ mod tests {
 #.........
 ......#...";
 
-    #[test]
-    fn test_no_panic() {
-        let input = PuzzleInput::get_input(DAY);
-        solve_a(&input);
-        // solve_b(&input); too slow
-    }
-
-    #[test]
-    fn test_solve_a() {
-        assert_eq!(solve_a(&PuzzleInput::new(TEST_INPUT)), 41);
-    }
-
-    #[test]
-    fn test_solve_b() {
-        assert_eq!(solve_b(&PuzzleInput::new(TEST_INPUT)), 6);
+    aoc_utils::day_tests! {
+        // solve_b is too slow in debug mode, it is only run by test_time_budget
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a;
+        test_solve_a: TEST_INPUT, solve_a => 41;
+        test_solve_b: TEST_INPUT, solve_b => 6;
     }
 }
@@ -1,11 +1,13 @@
 use aoc_utils::PuzzleInput;
 use itertools::Itertools;
+const YEAR: u16 = 2024;
 const DAY: u8 = 7;
 
+/// Symbols of the operators, indexed by their op id.
+const OPERATOR_SYMBOLS: [&str; 3] = ["+", "*", "||"];
+
 fn main() {
-    let input = PuzzleInput::get_input(DAY);
-    println!("A: {}", solve_a(&input));
-    println!("B: {}", solve_b(&input));
+    aoc_utils::run(YEAR, DAY, solve_a, solve_b);
 }
 
 struct Calculation {
@@ -28,7 +30,14 @@ impl Calculation {
     }
 
     fn can_be_calculated_from_inputs(&self, include_concat: bool) -> bool {
-        Calculation::possible_outputs(&self.inputs, include_concat).contains(&self.expected_output)
+        let possible = Calculation::possible_outputs(&self.inputs, include_concat)
+            .contains(&self.expected_output);
+        log::debug!(
+            "{}: {:?} can be calculated: {possible}",
+            self.expected_output,
+            self.inputs
+        );
+        possible
     }
 
     fn execute_operator(op_id: usize, input_a: u64, input_b: u64) -> u64 {
@@ -45,7 +54,7 @@ impl Calculation {
     fn possible_outputs(values: &[u64], include_concat: bool) -> Vec<u64> {
         let max_op_id = if include_concat { 2 } else { 1 };
         let op_sequences: Vec<Vec<usize>> = (1..values.len())
-            .map(|_| (0..=max_op_id))
+            .map(|_| 0..=max_op_id)
             .multi_cartesian_product()
             .collect();
 
@@ -56,6 +65,13 @@ impl Calculation {
             for (i, op) in op_seq.iter().enumerate() {
                 current_value = Calculation::execute_operator(*op, current_value, values[i+1]);
             }
+            log::trace!(
+                "{values:?} with operators {:?} = {current_value}",
+                op_seq
+                    .iter()
+                    .map(|op| OPERATOR_SYMBOLS[*op])
+                    .collect::<Vec<_>>()
+            );
             possible_results.push(current_value);
         }
 
@@ -83,6 +99,10 @@ fn solve_b(input: &PuzzleInput) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
+
+    // Both parts take about 3.5s in release mode on an input with 850 equations.
+    const TIME_BUDGET: Duration = Duration::from_secs(5);
 
     const TEST_INPUT: &str = "190: 10 19
 3267: 81 40 27
@@ -94,20 +114,10 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20";
 
-    #[test]
-    fn test_no_panic() {
-        let input = PuzzleInput::get_input(DAY);
-        solve_a(&input);
-        // solve_b(&input); too slow
-    }
-
-    #[test]
-    fn test_solve_a() {
-        assert_eq!(solve_a(&PuzzleInput::new(TEST_INPUT)), 3749);
-    }
-
-    #[test]
-    fn test_solve_b() {
-        assert_eq!(solve_b(&PuzzleInput::new(TEST_INPUT)), 11387);
+    aoc_utils::day_tests! {
+        // solve_b is too slow in debug mode, it is only run by test_time_budget
+        real_input(YEAR, DAY, TIME_BUDGET): solve_a;
+        test_solve_a: TEST_INPUT, solve_a => 3749;
+        test_solve_b: TEST_INPUT, solve_b => 11387;
     }
 }